@@ -6,6 +6,8 @@ use serde::{
 };
 use strum::Display;
 
+use crate::mode::Mode;
+
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Display, Deserialize)]
 pub enum Action {
     Tick,
@@ -17,8 +19,95 @@ pub enum Action {
     Refresh,
     Error(String),
     Help,
+    Mode(Mode),
 
     Previous,
     Next,
+    /// Sub-slide above or below, Previous and Next in decks without sub-slides.
+    Up,
+    Down,
+    First,
+    Last,
+    /// Slide before the last jump.
+    Back,
+    Forward,
+    /// Next letter marks current slide.
+    SetBookmark,
+    /// Next letter goes to slide marked with it.
+    JumpBookmark,
+    NextTab,
+    ScrollUp,
+    ScrollDown,
+    ScrollLeft,
+    ScrollRight,
     Reload,
+
+    ToggleEdit,
+    MoveSlideLeft,
+    MoveSlideRight,
+    DuplicateSlide,
+    DeleteSlide,
+
+    CaptureNote,
+    ToggleAnswered,
+    FocusTerminal,
+    ToggleProfile,
+    RunCode,
+    CopyCode,
+    ToggleOverview,
+    ToggleToc,
+    /// Pause or resume `--autoplay`.
+    ToggleAutoplay,
+    /// Wrap from the last slide to the first or stop there.
+    ToggleLoop,
+    /// Popup of bound keys, slide keys included.
+    ToggleHelp,
+    /// Black screen, navigation keeps working behind it.
+    ToggleBlank,
+    /// Keep showing the current frame while navigating, until toggled again.
+    ToggleFreeze,
+    /// Pane with speaker notes of the slide.
+    ToggleNotes,
+    /// Miniature of the next slide in the corner.
+    TogglePreview,
+    /// Pick item of the slide and draw it over the whole slide, again to leave zoom.
+    Zoom,
+    /// Mouse drags draw on the slide instead of changing slides.
+    ToggleDraw,
+    /// Draw with the next color, drawing mode starts too.
+    NextDrawColor,
+    /// Remove the last stroke drawn on the slide.
+    UndoStroke,
+    /// Remove drawing of the slide.
+    ClearDrawing,
+    /// Move laser pointer by columns and rows, shown in the middle of the slide first.
+    MovePointer(i16, i16),
+    /// Start the talk clock in the footer again.
+    ResetTimer,
+    /// Ask to press quit again, sent in kiosk mode.
+    ConfirmQuit,
+    /// Popup to find slide by fuzzy match of its title.
+    OpenPalette,
+    SearchPrompt,
+    /// Next slide matching the search.
+    SearchNext,
+    SearchPrevious,
+    ClearSearch,
+    /// Close the topmost overlay only, help popup before search and so on.
+    Cancel,
+    /// Slide number, counted from 1 as shown to the audience.
+    GotoSlide(usize),
+    /// Slide with `id`.
+    GotoId(String),
+    /// Go to `goto` of the first item on the slide with it.
+    FollowLink,
+    /// Ask for slide number to go to, the prompt starts with the text.
+    GotoPrompt(String),
+    /// Shell command run in the deck directory.
+    RunHook(String),
+    /// Key bindings of the current slide, checked before the ones of the mode.
+    SlideKeys(Vec<(String, Action)>),
+
+    /// Slide index and slide count after navigation.
+    SlideChanged(usize, usize),
 }
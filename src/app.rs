@@ -1,13 +1,22 @@
+use std::{collections::HashMap, time::Instant};
+
 use color_eyre::eyre::Result;
-use crossterm::event::KeyEvent;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::prelude::Rect;
 use serde::{Deserialize, Serialize};
 use tokio::sync::mpsc;
 
 use crate::{
     action::Action,
-    components::{slides::Slides, title::Title, Component},
-    config::Config,
+    capabilities::capabilities,
+    components::{
+        heartbeat::Heartbeat,
+        milestones::Milestones,
+        slides::{Slides, QUIT_CONFIRM_TIMEOUT},
+        title::Title,
+        Component,
+    },
+    config::{parse_key_sequence, Config},
     mode::Mode,
     tui,
 };
@@ -22,12 +31,23 @@ pub struct App {
     pub should_suspend: bool,
     pub mode: Mode,
     pub last_tick_key_events: Vec<KeyEvent>,
+    /// Key bindings of the current slide, used in home mode before the configured ones.
+    pub slide_keymap: HashMap<Vec<KeyEvent>, Action>,
+    /// Only quit is accepted, after asking to press it again.
+    pub kiosk: bool,
+    pub quit_requested: Option<Instant>,
 }
 
 impl App {
-    pub fn new(tick_rate: f64, frame_rate: f64, json_slides: String) -> Result<Self> {
+    pub fn new(
+        tick_rate: f64,
+        frame_rate: f64,
+        json_slides: String,
+        slides: Slides,
+    ) -> Result<Self> {
         let title = Title::new();
-        let slides = Slides::new();
+        let milestones = Milestones::new();
+        let heartbeat = Heartbeat::new();
         let config = Config::new()?;
 
         let mode = Mode::Home;
@@ -35,22 +55,94 @@ impl App {
             tick_rate,
             frame_rate,
             json_slides,
-            components: vec![Box::new(title), Box::new(slides)],
+            components: vec![
+                Box::new(title),
+                Box::new(slides),
+                Box::new(milestones),
+                Box::new(heartbeat),
+            ],
             should_quit: false,
             should_suspend: false,
             config,
             mode,
             last_tick_key_events: Vec::new(),
+            slide_keymap: HashMap::new(),
+            kiosk: false,
+            quit_requested: None,
         })
     }
 
+    /// Stop on the last slide even when `loop` of the config is on.
+    pub fn no_loop(mut self, no_loop: bool) -> Self {
+        if no_loop {
+            self.config.navigation.wrap = false;
+        }
+        self
+    }
+
+    /// Ignore keys other than quit, which has to be pressed twice.
+    pub fn kiosk(mut self, kiosk: bool) -> Self {
+        self.kiosk = kiosk;
+        self
+    }
+
+    /// Quit in kiosk mode when it was pressed again in time, other keys do nothing.
+    fn kiosk_action(&mut self, key: KeyEvent) -> Option<Action> {
+        if self.key_action(key)? != Action::Quit {
+            return None;
+        }
+        let now = Instant::now();
+        if self
+            .quit_requested
+            .is_some_and(|requested| now.duration_since(requested) < QUIT_CONFIRM_TIMEOUT)
+        {
+            return Some(Action::Quit);
+        }
+        self.quit_requested = Some(now);
+        Some(Action::ConfirmQuit)
+    }
+
+    /// Action of key, single key bindings are tried first, then the key sequence typed
+    /// since last tick.
+    fn key_action(&mut self, key: KeyEvent) -> Option<Action> {
+        let mut keymaps = vec![];
+        if self.mode == Mode::Home {
+            keymaps.push(&self.slide_keymap);
+        }
+        keymaps.extend(self.config.keybindings.get(&self.mode));
+        if keymaps.is_empty() {
+            return None;
+        }
+        if let Some(action) = keymaps.iter().find_map(|keymap| keymap.get(&vec![key])) {
+            return Some(action.clone());
+        }
+        self.last_tick_key_events.push(key);
+        let action = keymaps
+            .iter()
+            .find_map(|keymap| keymap.get(&self.last_tick_key_events))
+            .cloned();
+        match key.code {
+            // -- unbound digits start slide number, `27 Enter` jumps to slide 27
+            KeyCode::Char(c)
+                if action.is_none() && self.mode == Mode::Home && c.is_ascii_digit() =>
+            {
+                Some(Action::GotoPrompt(c.to_string()))
+            }
+            _ => action,
+        }
+    }
+
+    fn mouse_enabled(&self) -> bool {
+        self.config.mouse.enabled && capabilities().mouse && !self.kiosk
+    }
+
     pub async fn run(&mut self) -> Result<()> {
         let (action_tx, mut action_rx) = mpsc::unbounded_channel();
 
         let mut tui = tui::Tui::new()?
             .tick_rate(self.tick_rate)
-            .frame_rate(self.frame_rate);
-        // tui.mouse(true);
+            .frame_rate(self.frame_rate)
+            .mouse(self.mouse_enabled());
         tui.enter()?;
 
         for component in self.components.iter_mut() {
@@ -73,22 +165,15 @@ impl App {
                     tui::Event::Render => action_tx.send(Action::Render)?,
                     tui::Event::Resize(x, y) => action_tx.send(Action::Resize(x, y))?,
                     tui::Event::Key(key) => {
-                        if let Some(keymap) = self.config.keybindings.get(&self.mode) {
-                            if let Some(action) = keymap.get(&vec![key]) {
-                                log::info!("Got action: {action:?}");
-                                action_tx.send(action.clone())?;
-                            } else {
-                                // If the key was not handled as a single key action,
-                                // then consider it for multi-key combinations.
-                                self.last_tick_key_events.push(key);
-
-                                // Check for multi-key combinations
-                                if let Some(action) = keymap.get(&self.last_tick_key_events) {
-                                    log::info!("Got action: {action:?}");
-                                    action_tx.send(action.clone())?;
-                                }
-                            }
+                        let action = if self.kiosk {
+                            self.kiosk_action(key)
+                        } else {
+                            self.key_action(key)
                         };
+                        if let Some(action) = action {
+                            log::info!("Got action: {action:?}");
+                            action_tx.send(action)?;
+                        }
                     }
                     _ => {}
                 }
@@ -110,6 +195,21 @@ impl App {
                     Action::Quit => self.should_quit = true,
                     Action::Suspend => self.should_suspend = true,
                     Action::Resume => self.should_suspend = false,
+                    Action::Mode(mode) => self.mode = mode,
+                    Action::SlideKeys(ref keys) => {
+                        self.slide_keymap = keys
+                            .iter()
+                            .filter_map(|(key, action)| {
+                                Some((parse_key_sequence(key).ok()?, action.clone()))
+                            })
+                            .collect();
+                    }
+                    Action::ToggleEdit => {
+                        self.mode = match self.mode {
+                            Mode::Edit => Mode::Home,
+                            _ => Mode::Edit,
+                        };
+                    }
                     Action::Resize(w, h) => {
                         tui.resize(Rect::new(0, 0, w, h))?;
                         tui.draw(|f| {
@@ -148,8 +248,8 @@ impl App {
                 action_tx.send(Action::Resume)?;
                 tui = tui::Tui::new()?
                     .tick_rate(self.tick_rate)
-                    .frame_rate(self.frame_rate);
-                // tui.mouse(true);
+                    .frame_rate(self.frame_rate)
+                    .mouse(self.mouse_enabled());
                 tui.enter()?;
             } else if self.should_quit {
                 tui.stop()?;
@@ -0,0 +1,89 @@
+use std::{
+    io::ErrorKind,
+    path::Path,
+    process::{Child, Command, Stdio},
+};
+
+use crate::{
+    config::AudioConfig,
+    error::{io, Error, Result},
+};
+
+/// Plays slide audio by spawning the first available command line player.
+#[derive(Debug, Default)]
+pub struct AudioPlayer {
+    config: AudioConfig,
+    child: Option<Child>,
+}
+
+/// Player command with arguments for the file and volume in percent.
+fn player_commands(file: &Path, volume: u8) -> Vec<Command> {
+    let mut mpv = Command::new("mpv");
+    mpv.args(["--no-video", "--really-quiet"])
+        .arg(format!("--volume={}", volume))
+        .arg(file);
+    let mut ffplay = Command::new("ffplay");
+    ffplay
+        .args(["-nodisp", "-autoexit", "-loglevel", "quiet", "-volume"])
+        .arg(volume.to_string())
+        .arg(file);
+    let mut afplay = Command::new("afplay");
+    afplay
+        .arg("-v")
+        .arg(format!("{:.2}", volume as f32 / 100.0))
+        .arg(file);
+    let mut paplay = Command::new("paplay");
+    paplay
+        .arg(format!("--volume={}", volume as u32 * 65536 / 100))
+        .arg(file);
+    vec![mpv, ffplay, afplay, paplay]
+}
+
+impl AudioPlayer {
+    pub fn new(config: AudioConfig) -> Self {
+        Self {
+            config,
+            child: None,
+        }
+    }
+
+    /// Stop current playback and start playing `file`, nothing is played when muted.
+    pub fn play(&mut self, file: &Path) -> Result<()> {
+        self.stop();
+        if self.config.mute {
+            return Ok(());
+        }
+        let volume = self.config.volume.min(100);
+        for mut command in player_commands(file, volume) {
+            let child = command
+                .stdin(Stdio::null())
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match child {
+                Ok(child) => {
+                    self.child = Some(child);
+                    return Ok(());
+                }
+                Err(e) if e.kind() == ErrorKind::NotFound => continue,
+                Err(e) => return Err(io("failed to start audio player")(e)),
+            }
+        }
+        Err(Error::Render(String::from(
+            "no audio player found, install mpv or ffplay",
+        )))
+    }
+
+    pub fn stop(&mut self) {
+        if let Some(mut child) = self.child.take() {
+            let _ = child.kill();
+            let _ = child.wait();
+        }
+    }
+}
+
+impl Drop for AudioPlayer {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,60 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{io, Error, Result};
+
+/// Bookmarks are stored next to the slides file, letter to slide number (from 1).
+pub fn bookmarks_path(json_slides: &str) -> PathBuf {
+    let path = Path::new(json_slides);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("slides");
+    path.with_file_name(format!("{}.bookmarks.json", stem))
+}
+
+/// Bookmarks of the deck, missing file means none.
+pub fn read_bookmarks(json_slides: &str) -> BTreeMap<char, usize> {
+    std::fs::read_to_string(bookmarks_path(json_slides))
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+/// Mark slide (1-based) with `letter`, previous slide of the letter is replaced.
+pub fn set_bookmark(json_slides: &str, letter: char, slide: usize) -> Result<()> {
+    let mut bookmarks = read_bookmarks(json_slides);
+    bookmarks.insert(letter, slide);
+    let content = serde_json::to_string_pretty(&bookmarks)
+        .map_err(|e| Error::Parse(format!("invalid bookmarks: {}", e)))?;
+    std::fs::write(bookmarks_path(json_slides), content + "\n")
+        .map_err(io("failed to write bookmarks file"))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_bookmarks() {
+        let dir = std::env::temp_dir().join(format!("tui-slides-bookmarks-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let slides = dir.join("talk.json5").to_string_lossy().to_string();
+        assert!(read_bookmarks(&slides).is_empty());
+        set_bookmark(&slides, 'd', 12).unwrap();
+        set_bookmark(&slides, 'a', 40).unwrap();
+        set_bookmark(&slides, 'd', 14).unwrap();
+        let bookmarks = read_bookmarks(&slides);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(bookmarks, BTreeMap::from([('a', 40), ('d', 14)]));
+        assert_eq!(
+            bookmarks_path("deck/talk.json5"),
+            Path::new("deck/talk.bookmarks.json")
+        );
+    }
+}
@@ -0,0 +1,75 @@
+use std::{collections::HashSet, path::PathBuf, time::SystemTime};
+
+use crate::{
+    diagrams::{cache_dir, deck_diagrams, diagram_path},
+    enums::SlidesJson,
+    error::Result,
+};
+
+#[derive(Debug)]
+pub struct CacheEntry {
+    pub path: PathBuf,
+    pub size: u64,
+    pub modified: SystemTime,
+    /// File belongs to content of the current deck.
+    pub used: bool,
+}
+
+/// Files in cache of the deck, oldest first.
+pub fn cache_entries(slides: &SlidesJson, json_slides: &str) -> Result<Vec<CacheEntry>> {
+    let dir = cache_dir(json_slides);
+    if !dir.exists() {
+        return Ok(vec![]);
+    }
+    let used: HashSet<PathBuf> = deck_diagrams(slides)
+        .into_iter()
+        .map(|item| diagram_path(item, json_slides))
+        .collect();
+    let mut entries = vec![];
+    for entry in std::fs::read_dir(dir)? {
+        let entry = entry?;
+        let metadata = entry.metadata()?;
+        if !metadata.is_file() {
+            continue;
+        }
+        entries.push(CacheEntry {
+            used: used.contains(&entry.path()),
+            path: entry.path(),
+            size: metadata.len(),
+            modified: metadata.modified()?,
+        });
+    }
+    entries.sort_by_key(|e| e.modified);
+    Ok(entries)
+}
+
+/// Remove cached files of the deck, only files no longer used by it when `stale_only`.
+/// Returns number of removed files.
+pub fn clear(slides: &SlidesJson, json_slides: &str, stale_only: bool) -> Result<usize> {
+    let mut removed = 0;
+    for entry in cache_entries(slides, json_slides)? {
+        if stale_only && entry.used {
+            continue;
+        }
+        std::fs::remove_file(&entry.path)?;
+        removed += 1;
+    }
+    Ok(removed)
+}
+
+/// Keep cache under `max_bytes` by removing oldest files no longer used by the
+/// deck, files of current content are never removed. Returns number of removed files.
+pub fn prune(slides: &SlidesJson, json_slides: &str, max_bytes: u64) -> Result<usize> {
+    let entries = cache_entries(slides, json_slides)?;
+    let mut total: u64 = entries.iter().map(|e| e.size).sum();
+    let mut removed = 0;
+    for entry in entries.iter().filter(|e| !e.used) {
+        if total <= max_bytes {
+            break;
+        }
+        std::fs::remove_file(&entry.path)?;
+        total -= entry.size;
+        removed += 1;
+    }
+    Ok(removed)
+}
@@ -0,0 +1,159 @@
+use std::{env, str::FromStr, sync::OnceLock};
+
+use ratatui::style::Color;
+use ratatui_image::picker::Picker;
+
+/// How much of Unicode the terminal font and locale can be trusted with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum UnicodeLevel {
+    /// Plain ASCII only.
+    Ascii,
+    /// Box drawing and block elements, e.g. Linux console.
+    Basic,
+    /// Braille, symbols and the rest.
+    Full,
+}
+
+/// What the terminal is able to show, detected once at startup. All fallback
+/// decisions (color quantization, image protocol, glyph substitution) go through
+/// it instead of checking the environment in every builder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Capabilities {
+    pub truecolor: bool,
+    /// Terminal graphics (sixel, kitty, iTerm2) can be used, otherwise images are
+    /// drawn with half blocks.
+    pub images: bool,
+    pub unicode: UnicodeLevel,
+    pub mouse: bool,
+    /// Terminal size in cells at detection time.
+    pub size: (u16, u16),
+    /// Background of the terminal is light, code themes are picked to match it.
+    pub light: bool,
+}
+
+static CAPABILITIES: OnceLock<Capabilities> = OnceLock::new();
+
+/// Capabilities of the current terminal.
+pub fn capabilities() -> Capabilities {
+    *CAPABILITIES.get_or_init(Capabilities::detect)
+}
+
+/// Use `caps` instead of detected capabilities, e.g. when overridden on command line.
+/// Has to be called before the first `capabilities()`.
+pub fn init(caps: Capabilities) {
+    let _ = CAPABILITIES.set(caps);
+}
+
+/// Background of `COLORFGBG` (`fg;bg` or `fg;default;bg`, set by rxvt, Konsole and
+/// others) is one of the light colors of the 16 color palette.
+fn light_background(colorfgbg: &str) -> bool {
+    let bg = colorfgbg
+        .rsplit(';')
+        .next()
+        .and_then(|bg| bg.parse::<u8>().ok());
+    matches!(bg, Some(7 | 9..=15))
+}
+
+fn unicode_level(term: &str) -> UnicodeLevel {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|v| env::var(v).ok().filter(|l| !l.is_empty()));
+    match locale {
+        Some(l) if !l.to_lowercase().contains("utf") => UnicodeLevel::Ascii,
+        _ if term == "linux" => UnicodeLevel::Basic,
+        _ => UnicodeLevel::Full,
+    }
+}
+
+/// Nearest color of the xterm 256 color palette.
+fn ansi256(r: u8, g: u8, b: u8) -> u8 {
+    if r == g && g == b {
+        return match r {
+            0..=7 => 16,
+            249..=255 => 231,
+            v => 232 + ((v as u16 - 8) * 24 / 247) as u8,
+        };
+    }
+    let level = |v: u8| match v {
+        0..=47 => 0,
+        48..=114 => 1,
+        v => (v - 35) / 40,
+    };
+    16 + 36 * level(r) + 6 * level(g) + level(b)
+}
+
+impl Capabilities {
+    pub fn detect() -> Self {
+        let term = env::var("TERM").unwrap_or_default();
+        let colorterm = env::var("COLORTERM").unwrap_or_default();
+        Self {
+            truecolor: colorterm == "truecolor" || colorterm == "24bit" || term.ends_with("direct"),
+            images: Picker::from_termios().is_ok(),
+            unicode: unicode_level(&term),
+            mouse: term != "linux" && term != "dumb",
+            size: crossterm::terminal::size().unwrap_or((80, 24)),
+            light: light_background(&env::var("COLORFGBG").unwrap_or_default()),
+        }
+    }
+
+    /// Color as it should be sent to the terminal, RGB colors are mapped to 256
+    /// color palette when truecolor is not supported.
+    pub fn color(&self, color: Color) -> Color {
+        match color {
+            Color::Rgb(r, g, b) if !self.truecolor => Color::Indexed(ansi256(r, g, b)),
+            color => color,
+        }
+    }
+
+    /// Parse color from slides (`#RRGGBB` or name) and adapt it to the terminal.
+    pub fn parse_color(&self, color: &str) -> Option<Color> {
+        Color::from_str(color).ok().map(|c| self.color(c))
+    }
+
+    /// `glyph` when terminal supports `level`, `fallback` otherwise.
+    pub fn glyph<'a>(&self, level: UnicodeLevel, glyph: &'a str, fallback: &'a str) -> &'a str {
+        if self.unicode >= level {
+            glyph
+        } else {
+            fallback
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_color_quantization() {
+        let caps = Capabilities {
+            truecolor: false,
+            images: false,
+            unicode: UnicodeLevel::Ascii,
+            mouse: false,
+            size: (80, 24),
+            light: false,
+        };
+        assert_eq!(caps.parse_color("#FF0000"), Some(Color::Indexed(196)));
+        assert_eq!(caps.parse_color("#000000"), Some(Color::Indexed(16)));
+        assert_eq!(caps.parse_color("red"), Some(Color::Red));
+        assert_eq!(caps.glyph(UnicodeLevel::Full, "☑", "[x]"), "[x]");
+
+        let caps = Capabilities {
+            truecolor: true,
+            ..caps
+        };
+        assert_eq!(caps.parse_color("#FF0000"), Some(Color::Rgb(255, 0, 0)));
+    }
+
+    #[test]
+    fn test_light_background() {
+        assert!(light_background("0;15"));
+        assert!(light_background("0;default;7"));
+        assert!(!light_background("15;0"));
+        assert!(!light_background("7;8"));
+        assert!(!light_background(""));
+    }
+}
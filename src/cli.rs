@@ -1,6 +1,6 @@
-use std::path::PathBuf;
+use std::{path::PathBuf, time::Duration};
 
-use clap::Parser;
+use clap::{Parser, Subcommand, ValueEnum};
 
 use crate::utils::version;
 
@@ -29,8 +29,173 @@ pub struct Cli {
         short,
         long = "json",
         value_name = "PATH",
-        help = "Path to slides JSON file along with images folder",
+        help = "Path to slides JSON (or org, AsciiDoc) file along with images folder",
         default_value = ".data/slides.json5"
     )]
     pub json_slides: String,
+
+    #[arg(
+        long,
+        value_enum,
+        value_name = "PROTOCOL",
+        help = "Image protocol, use halfblocks when terminal graphics are not available (SSH, tmux)",
+        default_value_t = ImageProtocol::Auto
+    )]
+    pub image_protocol: ImageProtocol,
+
+    #[arg(
+        long,
+        help = "Never run renderers or fetch anything, uncached content shows placeholder"
+    )]
+    pub offline: bool,
+
+    #[arg(
+        long,
+        value_name = "THEME",
+        help = "Code highlighting theme, e.g. InspiredGitHub on light terminals, overrides theme of the deck"
+    )]
+    pub code_theme: Option<String>,
+
+    #[arg(
+        long,
+        help = "Terminal has light background, picks light code theme when not detected from COLORFGBG"
+    )]
+    pub light: bool,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Advance slides automatically, e.g. 8s, 500ms or 2m, Space pauses"
+    )]
+    pub autoplay: Option<Duration>,
+
+    #[arg(
+        long,
+        value_name = "DURATION",
+        value_parser = parse_duration,
+        help = "Length of the talk, e.g. 30m, the footer shows time left instead of time spent"
+    )]
+    pub duration: Option<Duration>,
+
+    #[arg(
+        long,
+        help = "Stop at the last slide instead of wrapping to the first, overrides config"
+    )]
+    pub no_loop: bool,
+
+    #[arg(
+        long,
+        help = "Ignore all keys but quit, pressed twice, and the mouse, e.g. for booth displays with --autoplay"
+    )]
+    pub kiosk: bool,
+
+    #[arg(
+        long,
+        help = "Presenter view of the instance showing the same slides file: notes, time and next slide"
+    )]
+    pub presenter: bool,
+
+    #[arg(
+        long,
+        help = "Record time spent on each slide, written to <slides>.rehearsal.csv on quit"
+    )]
+    pub rehearse: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+/// Duration like `8s`, `500ms`, `2m` or plain seconds `8`.
+pub fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit() && c != '.')
+        .unwrap_or(value.len());
+    let (number, unit) = value.split_at(split);
+    let number: f64 = number
+        .parse()
+        .map_err(|_| format!("invalid duration '{}'", value))?;
+    let seconds = match unit {
+        "ms" => number / 1000.0,
+        "" | "s" => number,
+        "m" => number * 60.0,
+        "h" => number * 3600.0,
+        _ => {
+            return Err(format!(
+                "unknown unit of duration '{}', use ms, s, m or h",
+                value
+            ))
+        }
+    };
+    Ok(Duration::from_secs_f64(seconds))
+}
+
+#[derive(ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageProtocol {
+    Auto,
+    Halfblocks,
+    Sixel,
+    Kitty,
+    Iterm2,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Command {
+    /// Export loaded slides (of any input format) to the canonical JSON schema
+    Export {
+        #[arg(long, help = "Export as JSON")]
+        json: bool,
+
+        #[arg(
+            short,
+            long,
+            value_name = "PATH",
+            help = "Output file, stdout is used when not set"
+        )]
+        output: Option<PathBuf>,
+    },
+    /// Render diagrams of the deck into cache before the talk, so slides show instantly
+    Warm {
+        #[arg(long, help = "Render again even when cached")]
+        force: bool,
+    },
+    /// Manage cache of rendered content next to the deck
+    Cache {
+        #[command(subcommand)]
+        command: CacheCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum CacheCommand {
+    /// Show size of the deck cache and how much of it is no longer used
+    Status,
+    /// Remove cached files of the deck
+    Clear {
+        #[arg(long, help = "Only remove files no longer used by the deck")]
+        stale: bool,
+    },
+    /// Render content into cache, same as `warm`
+    Warm {
+        #[arg(long, help = "Render again even when cached")]
+        force: bool,
+    },
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("8s"), Ok(Duration::from_secs(8)));
+        assert_eq!(parse_duration("8"), Ok(Duration::from_secs(8)));
+        assert_eq!(parse_duration("500ms"), Ok(Duration::from_millis(500)));
+        assert_eq!(parse_duration("1.5m"), Ok(Duration::from_secs(90)));
+        assert!(parse_duration("8 days").is_err());
+        assert!(parse_duration("s").is_err());
+    }
 }
@@ -11,6 +11,8 @@ use crate::{
 
 pub mod title;
 pub mod slides;
+pub mod milestones;
+pub mod heartbeat;
 
 /// `Component` is a trait that represents a visual and interactive element of the user interface.
 /// Implementors of this trait can be registered with the main application loop and will be able to receive events,
@@ -0,0 +1,113 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    net::UdpSocket,
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result;
+use ratatui::prelude::Rect;
+use serde_json::json;
+
+use super::{Component, Frame};
+use crate::{
+    action::Action,
+    config::{Config, HeartbeatConfig},
+};
+
+/// Periodically reports presentation state as JSON line to a file and/or UDP
+/// endpoint, so running presentations can be monitored from one place.
+#[derive(Default)]
+pub struct Heartbeat {
+    config: HeartbeatConfig,
+    json_slides: String,
+    socket: Option<UdpSocket>,
+    start: Option<Instant>,
+    last_beat: Option<Instant>,
+    frames: u32,
+    slide: usize,
+    slide_count: usize,
+}
+
+impl Heartbeat {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn is_enabled(&self) -> bool {
+        self.config.interval_secs > 0 && (self.config.file.is_some() || self.config.udp.is_some())
+    }
+
+    fn beat(&mut self) -> Result<()> {
+        let (Some(start), Some(last_beat)) = (self.start, self.last_beat) else {
+            return Ok(());
+        };
+        let since_last = last_beat.elapsed();
+        if since_last < Duration::from_secs(self.config.interval_secs) {
+            return Ok(());
+        }
+        let fps = self.frames as f64 / since_last.as_secs_f64();
+        self.frames = 0;
+        self.last_beat = Some(Instant::now());
+
+        let line = json!({
+            "time": chrono::Local::now().to_rfc3339(),
+            "deck": self.json_slides,
+            "slide": self.slide + 1,
+            "slides": self.slide_count,
+            "elapsed_secs": start.elapsed().as_secs(),
+            "fps": (fps * 10.0).round() / 10.0,
+        })
+        .to_string();
+
+        if let Some(file) = &self.config.file {
+            let mut file = OpenOptions::new().create(true).append(true).open(file)?;
+            writeln!(file, "{}", line)?;
+        }
+        if let (Some(socket), Some(udp)) = (&self.socket, &self.config.udp) {
+            socket.send_to(line.as_bytes(), udp)?;
+        }
+        Ok(())
+    }
+}
+
+impl Component for Heartbeat {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config.heartbeat;
+        Ok(())
+    }
+
+    fn init(&mut self, area: Rect, json_slides: String) -> Result<()> {
+        self.json_slides = json_slides;
+        if !self.is_enabled() {
+            return Ok(());
+        }
+        if self.config.udp.is_some() {
+            self.socket = Some(UdpSocket::bind("0.0.0.0:0")?);
+        }
+        self.start = Some(Instant::now());
+        self.last_beat = self.start;
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        match action {
+            Action::SlideChanged(slide, slide_count) => {
+                self.slide = slide;
+                self.slide_count = slide_count;
+            }
+            Action::Render => self.frames += 1,
+            Action::Tick => {
+                if let Err(e) = self.beat() {
+                    log::error!("Failed to send heartbeat: {e:?}");
+                }
+            }
+            _ => {}
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        Ok(())
+    }
+}
@@ -0,0 +1,89 @@
+use std::{
+    io::ErrorKind,
+    process::{Command, Stdio},
+    time::{Duration, Instant},
+};
+
+use color_eyre::eyre::Result;
+use ratatui::prelude::Rect;
+
+use super::{Component, Frame};
+use crate::{
+    action::Action,
+    config::{Config, NotificationsConfig},
+    osc,
+};
+
+/// Sends desktop notifications when configured time since start is reached, so
+/// presenter is warned about time even without visible clock.
+#[derive(Default)]
+pub struct Milestones {
+    config: NotificationsConfig,
+    start: Option<Instant>,
+    sent: usize,
+}
+
+impl Milestones {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn send(&self, message: &str) {
+        if self.config.osc {
+            if let Err(e) = osc::notify_with_title("tui-slides", message) {
+                log::error!("Failed to send terminal notification: {e:?}");
+            }
+        }
+        if self.config.notify_send {
+            let child = Command::new("notify-send")
+                .arg("tui-slides")
+                .arg(message)
+                .stdout(Stdio::null())
+                .stderr(Stdio::null())
+                .spawn();
+            match child {
+                Ok(_) => {}
+                Err(e) if e.kind() == ErrorKind::NotFound => {}
+                Err(e) => log::error!("Failed to run notify-send: {e:?}"),
+            }
+        }
+    }
+
+    fn check(&mut self) {
+        let Some(start) = self.start else {
+            return;
+        };
+        let elapsed = start.elapsed();
+        while let Some(milestone) = self.config.milestones.get(self.sent) {
+            if elapsed < Duration::from_secs(milestone.minutes * 60) {
+                break;
+            }
+            self.send(&milestone.message);
+            self.sent += 1;
+        }
+    }
+}
+
+impl Component for Milestones {
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.config = config.notifications;
+        self.config.milestones.sort_by_key(|m| m.minutes);
+        Ok(())
+    }
+
+    fn init(&mut self, area: Rect, json_slides: String) -> Result<()> {
+        self.start = Some(Instant::now());
+        Ok(())
+    }
+
+    fn update(&mut self, action: Action) -> Result<Option<Action>> {
+        if action == Action::Tick {
+            self.check();
+        }
+        Ok(None)
+    }
+
+    fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        Ok(())
+    }
+}
@@ -1,136 +1,2295 @@
+use std::collections::{BTreeMap, HashMap};
 use std::io::Read;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use block::Position;
 use color_eyre::{eyre::Result, owo_colors::OwoColorize};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind};
 use ratatui::{
     prelude::*,
     style::Stylize,
     widgets::{block::Title, *},
 };
-use ratatui_image::{picker::Picker, protocol::StatefulProtocol, Image, Resize, StatefulImage};
-use syntect::{
-    easy::HighlightLines, highlighting::ThemeSet, parsing::SyntaxSet, util::LinesWithEndings,
+use ratatui_image::{
+    picker::{Picker, ProtocolType},
+    protocol::StatefulProtocol,
+    Image, Resize, StatefulImage,
 };
-use syntect_tui::into_span;
-use tokio::sync::mpsc::UnboundedSender;
+use tokio::sync::{mpsc::UnboundedSender, oneshot};
+use tokio_util::sync::CancellationToken;
 use tui_big_text::{BigText, PixelSize};
 
 use super::{Component, Frame};
 use crate::{
     action::Action,
-    enums::{ContentJson, ReturnSlideWidget, SlideContentType, SlideJson, SlidesJson},
-    layout::{get_slides_layout, CONTENT_HEIGHT, CONTENT_WIDTH},
+    audio::AudioPlayer,
+    bookmarks::{read_bookmarks, set_bookmark},
+    capabilities::{capabilities, UnicodeLevel},
+    cli::ImageProtocol,
+    config::{
+        parse_key_sequence, CodeConfig, Config, ExecConfig, NavigationConfig, ProgressConfig,
+        RenderConfig, TerminalConfig,
+    },
+    diagrams::{content_hash, render_diagrams},
+    enums::{
+        ContentJson, MenuOptionJson, ReturnSlideWidget, SlideContentType, SlideJson, SlidesJson,
+        Typewriter,
+    },
+    exec::{self, ExecRun, ExecState},
+    highlight::{
+        callout_legend, code_rows, code_width, deck_code_blocks, detect_language, emphasize_lines,
+        gutter_width, mark_callouts, number_lines, step_line_counts, type_lines, typewriter_units,
+        CodeBlock, HighlightCache,
+    },
+    history::History,
+    layout::{
+        get_notes_layout, get_slides_layout, get_toc_layout, SlidesLayout, CONTENT_HEIGHT,
+        CONTENT_WIDTH,
+    },
+    loaders::{deck_dir, present_slides, read_slides},
+    locale::{set_locale, Locale},
+    mode::Mode,
+    navigation::{self, count_steps, flow_slides, jump, numbered_slide, Step},
+    notes::{self, append_note, read_notes, Note},
+    osc,
+    presenter::{pace, socket_path, unix_now, PresenterFollower, PresenterServer, PresenterState},
+    pty::PtySession,
+    rehearsal::Rehearsal,
+    search::{fuzzy_titles, matching_slides, next_match, slide_text, slide_with_id},
     slide_builder::{
-        get_slide_content_string, make_slide_block, make_slide_content, make_slide_image,
+        diagram_image_item, get_slide_content_string, load_image, make_slide_block,
+        make_slide_content, make_slide_image_frames,
     },
+    theme::{parse_color, set_theme, theme, Theme},
+    utils::get_config_dir,
+    widgets::{
+        annotation::{Annotation, AnnotationLayer, DRAW_COLORS},
+        countdown::{format_duration, talk_remaining},
+        help::{key_help, Help},
+        menu::MenuWidget,
+        miniature::{miniature_size, Miniature},
+        nav_hud::{marker_at, NavHud, NAV_HUD_HEIGHT},
+        overview::{grid_columns, Overview, SlidePreview},
+        palette::Palette,
+        pointer::{moved, Pointer},
+        progress::{deck_progress, ProgressBar},
+        prompt::{InputPrompt, PromptEvent},
+        questions::QuestionList,
+        terminal::TerminalWidget,
+        toc::Toc,
+    },
+    writer::{edit_slide, save_path, write_slides, SlideEdit},
 };
 
-pub struct Slides {
-    action_tx: Option<UnboundedSender<Action>>,
-    json_slides: String,
-    slides: Option<SlidesJson>,
-    slide_index: usize,
-    slide_count: usize,
-    picker: Picker,
-    images: Vec<Box<dyn StatefulProtocol>>,
-}
+/// Built widget of slide item, `None` for images which are drawn from `Slides::images`.
+type SlideItem<'a> = (Option<ReturnSlideWidget<'a>>, ContentJson);
+
+const TAB_BAR_HEIGHT: u16 = 2;
+
+fn format_bytes(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1 << 20 => format!("{:.1} MiB", b as f64 / (1 << 20) as f64),
+        b if b >= 1 << 10 => format!("{:.1} KiB", b as f64 / (1 << 10) as f64),
+        b => format!("{} B", b),
+    }
+}
+
+/// Terminal of slide item, REPL sessions are kept when leaving the slide.
+struct ItemPty {
+    session: PtySession,
+    repl: bool,
+}
+
+/// Rows of exec output pane below code block `height` rows high.
+fn exec_output_height(height: u16) -> u16 {
+    (height / 2).max(3).min(height)
+}
+
+/// Font size assumed when terminal does not report its pixel size.
+const DEFAULT_FONT_SIZE: (u16, u16) = (8, 16);
+const MIN_FRAME_DELAY: Duration = Duration::from_millis(20);
+const DEFAULT_FRAME_DELAY: Duration = Duration::from_millis(100);
+/// Navigation strip hides after this long without the mouse at the bottom edge.
+const NAV_HUD_TIMEOUT: Duration = Duration::from_secs(2);
+/// Slide with `confirm_advance` is left when Next or Previous is pressed again within
+/// this time.
+const ADVANCE_CONFIRM_TIMEOUT: Duration = Duration::from_secs(2);
+/// Kiosk mode quits when quit is pressed again within this time.
+pub const QUIT_CONFIRM_TIMEOUT: Duration = Duration::from_secs(3);
+/// Wheel events closer than this go to one slide, trackpads send them in bursts.
+const WHEEL_NAV_INTERVAL: Duration = Duration::from_millis(300);
+/// Columns wide code is scrolled by on one key press.
+const H_SCROLL_STEP: u16 = 4;
+const END_SCREEN_TITLE: &str = "THE END";
+const DEFAULT_TYPEWRITER_CHAR_MS: u64 = 30;
+const DEFAULT_TYPEWRITER_LINE_MS: u64 = 300;
+
+/// Item of the slide picked with `z`, drawn over the whole slide once zoomed. Index is
+/// of the flattened items of the slide.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Zoom {
+    Selecting(usize),
+    Zoomed(usize),
+}
+
+/// Bookmark key press waiting for its letter.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum BookmarkKey {
+    Set,
+    Jump,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PromptKind {
+    Note,
+    Goto,
+    Search,
+}
+
+/// Image of slide item ready to be drawn, or why it could not be loaded.
+enum ImageSlot {
+    Ready(Box<dyn StatefulProtocol>),
+    Failed(String),
+}
+
+/// Images of a slide decoded on background task.
+struct LoadedImages {
+    images: Vec<ImageSlot>,
+    animations: Vec<ImageAnimation>,
+    /// Size of decoded pixels of all images and animation frames.
+    bytes: usize,
+}
+
+/// Decoded frames of animated image shown at `image_index` of `Slides::images`.
+struct ImageAnimation {
+    image_index: usize,
+    frames: Vec<(Box<dyn StatefulProtocol>, Duration)>,
+    frame: usize,
+    frame_start: Instant,
+}
+
+pub struct Slides {
+    action_tx: Option<UnboundedSender<Action>>,
+    json_slides: String,
+    slides: Option<SlidesJson>,
+    /// Deck as written in the file, edit mode changes and saves it.
+    source: Option<SlidesJson>,
+    slide_index: usize,
+    slide_count: usize,
+    picker: Picker,
+    image_protocol: ImageProtocol,
+    images: Vec<ImageSlot>,
+    animations: Vec<ImageAnimation>,
+    /// Images of current slide still being decoded, cancelled when slide is left.
+    images_loading: Option<(CancellationToken, oneshot::Receiver<LoadedImages>)>,
+    image_bytes: usize,
+    tab_index: usize,
+    scroll: u16,
+    /// Columns wide code lines are scrolled by.
+    h_scroll: u16,
+    edit_mode: bool,
+    prompt: Option<(PromptKind, InputPrompt)>,
+    question_index: usize,
+    /// Session notes listed by `Questions` items, read on slide enter and after they
+    /// change so drawing does not hit the file.
+    questions: Vec<Note>,
+    /// Selected option of `Menu` item on the slide.
+    menu_index: usize,
+    slide_start: Instant,
+    reveal_all: bool,
+    /// Steps of code items with `steps` revealed by Next on current slide.
+    code_step: usize,
+    audio: AudioPlayer,
+    terminal: TerminalConfig,
+    render: RenderConfig,
+    exec: ExecConfig,
+    code: CodeConfig,
+    /// Runs of code blocks on current slide by item index.
+    exec_runs: HashMap<usize, ExecRun>,
+    offline: bool,
+    code_theme: Option<String>,
+    highlights: HighlightCache,
+    /// Slide changed since last draw, entering it (images, audio, ...) is done once
+    /// on draw so held navigation keys do not queue work for every skipped slide.
+    needs_enter: bool,
+    /// Embedded terminals of current slide and REPLs of the whole deck by `pty_key`.
+    ptys: HashMap<u64, ItemPty>,
+    /// Keys are sent to the first terminal instead of the app.
+    pty_focus: bool,
+    /// Memory usage of caches is shown over the slide.
+    show_profile: bool,
+    /// Area of the last draw, for mapping mouse positions.
+    area: Rect,
+    /// Slide box of the last draw, its bottom border has the slide counter.
+    content_area: Rect,
+    /// Last wheel event which changed slide.
+    wheel_navigated: Option<Instant>,
+    nav_hud_until: Option<Instant>,
+    nav_hud_hovered: Option<usize>,
+    /// Slide selected in overview grid while it is open.
+    overview: Option<usize>,
+    /// Query typed in title palette and selected match, while it is open.
+    palette: Option<(String, usize)>,
+    /// Slides matching the last search, `n` and `N` move between them.
+    search_matches: Vec<usize>,
+    zoom: Option<Zoom>,
+    show_toc: bool,
+    show_notes: bool,
+    /// Miniature of the next slide in the corner.
+    show_preview: bool,
+    /// Laser pointer position in the terminal, hidden when `None`.
+    pointer: Option<(u16, u16)>,
+    /// Mouse drags draw on the slide instead of clicks changing slides.
+    drawing: bool,
+    /// Index of color in `DRAW_COLORS` of the next stroke.
+    draw_color: usize,
+    /// Drawings by slide index, kept while moving between slides.
+    annotations: HashMap<usize, Annotation>,
+    /// Key bindings of home mode, listed by help overlay with those of the slide.
+    keybindings: HashMap<Vec<KeyEvent>, Action>,
+    show_help: bool,
+    /// Screen is black so the audience looks at the speaker.
+    blank: bool,
+    /// Shows notes, time and next slide of the instance presenting the deck.
+    presenter_view: bool,
+    presenter_server: Option<PresenterServer>,
+    /// State of the presenting instance followed by presenter view.
+    presenter_follower: Option<PresenterFollower>,
+    /// Talk clock shown in the footer, started by the first slide change or reset when
+    /// the talk actually starts.
+    talk_started: Option<Instant>,
+    /// Planned length of the talk, the footer counts down to it.
+    talk_duration: Option<Duration>,
+    /// Time spent on slides with `--rehearse`, written as report on quit.
+    rehearsal: Option<Rehearsal>,
+    /// Slide of the presenting instance last followed, keys of presenter view can peek
+    /// at other slides until it changes.
+    followed_slide: Option<usize>,
+    /// Error of the last reload, the deck loaded before is shown meanwhile.
+    reload_error: Option<String>,
+    /// First press of Next or Previous on slide with `confirm_advance`.
+    advance_requested: Option<Instant>,
+    /// Kiosk mode asks to press quit again until then.
+    quit_confirm_until: Option<Instant>,
+    /// Frame shown instead of the slide while frozen, taken on the next draw when
+    /// freezing starts.
+    frozen: Option<Option<Buffer>>,
+    history: History,
+    bookmark_key: Option<BookmarkKey>,
+    /// Interval of `--autoplay`, measured from the last advance.
+    autoplay: Option<Duration>,
+    autoplay_paused: bool,
+    autoplay_since: Instant,
+    navigation: NavigationConfig,
+    progress: ProgressConfig,
+    /// End of deck screen is shown after the last slide.
+    at_end: bool,
+}
+
+impl Default for Slides {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Slides {
+    pub fn new() -> Self {
+        Self {
+            action_tx: None,
+            json_slides: String::from(""),
+            slides: None,
+            source: None,
+            slide_index: 0,
+            slide_count: 0,
+            picker: Picker::from_termios().unwrap_or_else(|_| Picker::new(DEFAULT_FONT_SIZE)),
+            image_protocol: ImageProtocol::Auto,
+            images: vec![],
+            animations: vec![],
+            images_loading: None,
+            image_bytes: 0,
+            tab_index: 0,
+            scroll: 0,
+            h_scroll: 0,
+            edit_mode: false,
+            prompt: None,
+            question_index: 0,
+            questions: vec![],
+            menu_index: 0,
+            slide_start: Instant::now(),
+            reveal_all: false,
+            code_step: 0,
+            audio: AudioPlayer::default(),
+            terminal: TerminalConfig::default(),
+            render: RenderConfig::default(),
+            exec: ExecConfig::default(),
+            code: CodeConfig::default(),
+            exec_runs: HashMap::new(),
+            offline: false,
+            code_theme: None,
+            highlights: HighlightCache::default(),
+            needs_enter: false,
+            ptys: HashMap::new(),
+            pty_focus: false,
+            show_profile: false,
+            area: Rect::default(),
+            content_area: Rect::default(),
+            wheel_navigated: None,
+            nav_hud_until: None,
+            nav_hud_hovered: None,
+            overview: None,
+            palette: None,
+            search_matches: vec![],
+            zoom: None,
+            show_toc: false,
+            show_notes: false,
+            show_preview: false,
+            pointer: None,
+            drawing: false,
+            draw_color: 0,
+            annotations: HashMap::new(),
+            keybindings: HashMap::new(),
+            show_help: false,
+            blank: false,
+            quit_confirm_until: None,
+            advance_requested: None,
+            reload_error: None,
+            presenter_view: false,
+            presenter_server: None,
+            presenter_follower: None,
+            talk_started: None,
+            talk_duration: None,
+            rehearsal: None,
+            followed_slide: None,
+            frozen: None,
+            history: History::default(),
+            bookmark_key: None,
+            autoplay: None,
+            autoplay_paused: false,
+            autoplay_since: Instant::now(),
+            navigation: NavigationConfig::default(),
+            progress: ProgressConfig::default(),
+            at_end: false,
+        }
+    }
+
+    pub fn image_protocol(mut self, image_protocol: ImageProtocol) -> Self {
+        self.image_protocol = image_protocol;
+        self
+    }
+
+    /// Only use cached content, nothing is rendered or fetched while presenting.
+    pub fn offline(mut self, offline: bool) -> Self {
+        self.offline = offline;
+        self
+    }
+
+    /// Advance slides every `interval`, like pressing Next.
+    pub fn autoplay(mut self, interval: Option<Duration>) -> Self {
+        self.autoplay = interval;
+        self
+    }
+
+    /// Show time left of talk of `duration` in the footer.
+    pub fn duration(mut self, duration: Option<Duration>) -> Self {
+        self.talk_duration = duration;
+        self
+    }
+
+    /// Record time spent on each slide and write report next to the deck on quit.
+    pub fn rehearse(mut self, rehearse: bool) -> Self {
+        self.rehearsal = rehearse.then(Rehearsal::default);
+        self
+    }
+
+    /// Follow the instance showing the same deck instead of presenting it.
+    pub fn presenter_view(mut self, presenter_view: bool) -> Self {
+        self.presenter_view = presenter_view;
+        self
+    }
+
+    /// Code theme chosen on command line, wins over theme of the deck but not over
+    /// theme of the code block.
+    pub fn code_theme(mut self, code_theme: Option<String>) -> Self {
+        self.code_theme = code_theme;
+        self
+    }
+
+    fn code_block(&self, item: &ContentJson) -> CodeBlock {
+        let deck_theme = self.slides.as_ref().and_then(|s| s.code_theme.as_deref());
+        CodeBlock::new(item, self.code_theme.as_deref(), deck_theme)
+    }
+
+    /// Highlight code of the whole deck ahead, so first visit of a slide is instant.
+    fn warm_highlights(&self) {
+        if let Some(slides) = &self.slides {
+            let blocks = deck_code_blocks(slides, self.code_theme.as_deref());
+            self.highlights.warm(blocks);
+        }
+    }
+
+    /// Pick image protocol, terminal graphics need known font size so half blocks
+    /// are used whenever the terminal does not report it (e.g. over SSH).
+    fn init_picker(&mut self) {
+        if !capabilities().images {
+            self.picker.protocol_type = ProtocolType::Halfblocks;
+            if self.image_protocol == ImageProtocol::Auto {
+                return;
+            }
+        }
+        self.picker.protocol_type = match self.image_protocol {
+            ImageProtocol::Auto => self.picker.guess_protocol(),
+            ImageProtocol::Halfblocks => ProtocolType::Halfblocks,
+            ImageProtocol::Sixel => ProtocolType::Sixel,
+            ImageProtocol::Kitty => ProtocolType::Kitty,
+            ImageProtocol::Iterm2 => ProtocolType::Iterm2,
+        };
+    }
+
+    fn get_json_slides(&mut self) -> crate::error::Result<()> {
+        let source = read_slides(&self.json_slides)?;
+        let slides = present_slides(&source, &self.json_slides)?;
+        let theme = match &slides.theme {
+            Some(dir) => Theme::load(&deck_dir(&self.json_slides).join(dir))?,
+            None => Theme::default(),
+        };
+        let locale = slides.locale.as_deref().map(Locale::from_tag).transpose()?;
+        let syntax_dirs: Vec<PathBuf> = [
+            self.code
+                .syntaxes
+                .as_ref()
+                .map(|dir| get_config_dir().join(dir)),
+            slides
+                .syntaxes
+                .as_ref()
+                .map(|dir| deck_dir(&self.json_slides).join(dir)),
+        ]
+        .into_iter()
+        .flatten()
+        .collect();
+        self.highlights.set_syntax_dirs(syntax_dirs)?;
+        set_theme(theme);
+        set_locale(locale);
+        if self.offline {
+            log::info!("Offline, only cached diagrams are shown");
+        } else {
+            render_diagrams(&slides, &self.json_slides);
+        }
+
+        self.slide_count = slides.slides.len();
+        self.slide_index = self.slide_index.min(self.slide_count.saturating_sub(1));
+        self.history.retain(self.slide_count);
+        self.slides = Some(slides);
+        self.source = Some(source);
+        self.warm_highlights();
+        Ok(())
+    }
+
+    fn get_slide(&self) -> SlideJson {
+        if let Some(slide) = self
+            .slides
+            .as_ref()
+            .and_then(|s| s.slides.get(self.slide_index))
+        {
+            return slide.clone();
+        }
+        SlideJson::new(None, vec![])
+    }
+
+    fn get_slide_rect(&self, rect: Rect, item_rect: Option<Rect>) -> Rect {
+        let mut slide_rect = Rect::new(rect.x, rect.y, rect.width, rect.height);
+        if let Some(slides) = &self.slides {
+            if let Some(s_content_rect) = item_rect {
+                slide_rect.x += s_content_rect.x;
+                slide_rect.y += s_content_rect.y;
+                slide_rect.width = s_content_rect.width;
+                slide_rect.height = s_content_rect.height;
+            }
+        }
+        slide_rect
+    }
+
+    /// Image items of the slide with diagrams replaced by their rendered images.
+    fn image_items(&self, slide: &SlideJson) -> Vec<ContentJson> {
+        self.flatten_items(slide)
+            .into_iter()
+            .map(|item| diagram_image_item(&item, &self.json_slides).unwrap_or(item))
+            .filter(|item| item.type_ == SlideContentType::Image)
+            .collect()
+    }
+
+    /// Decode images of the current slide on blocking task, images which are still
+    /// loading for previous slide are cancelled so skipped slides cost nothing.
+    fn store_images(&mut self) {
+        if let Some((token, _)) = self.images_loading.take() {
+            token.cancel();
+        }
+        self.images.clear();
+        self.animations.clear();
+        self.image_bytes = 0;
+
+        let items = self.image_items(&self.get_slide());
+        if items.is_empty() {
+            return;
+        }
+        let token = CancellationToken::new();
+        let (tx, rx) = oneshot::channel();
+        let picker = self.picker;
+        let json_slides = self.json_slides.clone();
+        let cancel = token.clone();
+        tokio::task::spawn_blocking(move || {
+            if let Some(loaded) = Self::load_images(&items, &json_slides, picker, &cancel) {
+                let _ = tx.send(loaded);
+            }
+        });
+        self.images_loading = Some((token, rx));
+    }
+
+    /// `None` when cancelled, checked between images and animation frames.
+    fn load_images(
+        items: &[ContentJson],
+        json_slides: &str,
+        mut picker: Picker,
+        cancel: &CancellationToken,
+    ) -> Option<LoadedImages> {
+        let mut loaded = LoadedImages {
+            images: vec![],
+            animations: vec![],
+            bytes: 0,
+        };
+        for item in items {
+            if cancel.is_cancelled() {
+                return None;
+            }
+            if let Some(frames) = make_slide_image_frames(item, json_slides) {
+                let mut protocols: Vec<(Box<dyn StatefulProtocol>, Duration)> = vec![];
+                for (img, delay) in frames {
+                    if cancel.is_cancelled() {
+                        return None;
+                    }
+                    loaded.bytes += img.as_bytes().len();
+                    protocols.push((picker.new_resize_protocol(img), delay));
+                }
+                loaded.images.push(ImageSlot::Ready(protocols[0].0.clone()));
+                loaded.animations.push(ImageAnimation {
+                    image_index: loaded.images.len() - 1,
+                    frames: protocols,
+                    frame: 0,
+                    frame_start: Instant::now(),
+                });
+                continue;
+            }
+            loaded.images.push(match load_image(item, json_slides) {
+                Ok(img) => {
+                    loaded.bytes += img.as_bytes().len();
+                    ImageSlot::Ready(picker.new_resize_protocol(img))
+                }
+                Err(e) => ImageSlot::Failed(e.report()),
+            });
+        }
+        Some(loaded)
+    }
+
+    fn receive_images(&mut self) {
+        let Some((_, rx)) = &mut self.images_loading else {
+            return;
+        };
+        match rx.try_recv() {
+            Ok(loaded) => {
+                self.images = loaded.images;
+                self.animations = loaded.animations;
+                self.image_bytes = loaded.bytes;
+                self.images_loading = None;
+            }
+            Err(oneshot::error::TryRecvError::Empty) => {}
+            Err(oneshot::error::TryRecvError::Closed) => self.images_loading = None,
+        }
+    }
+
+    /// Command of `Terminal` item, or of `Repl` item the configured REPL of its
+    /// language unless `content` sets one.
+    fn pty_command(&self, item: &ContentJson) -> String {
+        let command = get_slide_content_string(item);
+        if item.type_ != SlideContentType::Repl || !command.trim().is_empty() {
+            return command;
+        }
+        let language = item.language.as_deref().unwrap_or_default();
+        self.exec.repls.get(language).cloned().unwrap_or(command)
+    }
+
+    /// Terminal items of current slide with their session keys.
+    fn pty_items(&self) -> Vec<(u64, ContentJson)> {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .into_iter()
+            .filter(|i| matches!(i.type_, SlideContentType::Terminal | SlideContentType::Repl))
+            .map(|item| {
+                let key = format!(
+                    "{}\0{:?}\0{}",
+                    self.slide_index,
+                    item.rect,
+                    self.pty_command(&item)
+                );
+                (content_hash(&key), item)
+            })
+            .collect()
+    }
+
+    /// Start commands of `Terminal` and `Repl` items, terminals of previous slide are
+    /// killed while REPLs keep running with their history until the deck is closed.
+    fn start_terminals(&mut self) {
+        if self.presenter_view {
+            return;
+        }
+        self.ptys.retain(|_, pty| pty.repl);
+        self.pty_focus = false;
+
+        let cwd = deck_dir(&self.json_slides).to_path_buf();
+        for (key, item) in self.pty_items() {
+            if self.ptys.contains_key(&key) {
+                continue;
+            }
+            let rect = item.rect.unwrap_or_default();
+            let rows = rect.height.saturating_sub(2).max(1);
+            let cols = rect.width.saturating_sub(2).max(1);
+            let command = self.pty_command(&item);
+            match PtySession::spawn(&command, &cwd, rows, cols) {
+                Ok(session) => {
+                    let repl = item.type_ == SlideContentType::Repl;
+                    self.ptys.insert(key, ItemPty { session, repl });
+                }
+                Err(e) => log::error!("Failed to start terminal: {}", e.report()),
+            }
+        }
+    }
+
+    /// Key of the first terminal of current slide, the one typed into when focused.
+    fn focused_pty(&self) -> Option<u64> {
+        self.pty_items()
+            .into_iter()
+            .map(|(key, _)| key)
+            .find(|key| self.ptys.contains_key(key))
+    }
+
+    fn focus_terminal(&mut self) -> Option<Action> {
+        self.focused_pty()?;
+        self.pty_focus = true;
+        Some(Action::Mode(Mode::Input))
+    }
+
+    /// Forward key to focused terminal, `Ctrl-]` (like telnet) gives keys back.
+    fn handle_terminal_key(&mut self, key: KeyEvent) -> Option<Action> {
+        if key.code == KeyCode::Char(']') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.pty_focus = false;
+            return Some(Action::Mode(Mode::Home));
+        }
+        let focused = self.focused_pty();
+        if let Some(pty) = focused.and_then(|key| self.ptys.get_mut(&key)) {
+            if let Err(e) = pty.session.write_key(key) {
+                log::error!("Failed to send key to terminal: {}", e.report());
+            }
+        }
+        None
+    }
+
+    /// Send Next once autoplay interval is over, not while the presenter is typing
+    /// into prompt, overview or terminal.
+    fn advance_autoplay(&mut self) {
+        let Some(interval) = self.autoplay else {
+            return;
+        };
+        let busy = self.prompt.is_some() || self.overview.is_some() || self.pty_focus;
+        let demo = self.get_slide().confirm_advance == Some(true);
+        if self.autoplay_paused || demo || busy || self.autoplay_since.elapsed() < interval {
+            return;
+        }
+        self.autoplay_since = Instant::now();
+        if let Some(tx) = &self.action_tx {
+            let _ = tx.send(Action::Next);
+        }
+    }
+
+    fn advance_animations(&mut self) {
+        for animation in self.animations.iter_mut() {
+            let mut delay = animation.frames[animation.frame].1;
+            if delay < MIN_FRAME_DELAY {
+                delay = DEFAULT_FRAME_DELAY;
+            }
+            if animation.frame_start.elapsed() < delay {
+                continue;
+            }
+            animation.frame = (animation.frame + 1) % animation.frames.len();
+            animation.frame_start = Instant::now();
+            self.images[animation.image_index] =
+                ImageSlot::Ready(animation.frames[animation.frame].0.clone());
+        }
+    }
+
+    /// Slide items as they are rendered, `Tabs` items are followed by the content of
+    /// the active tab with rects moved into the tab pane.
+    fn flatten_items(&self, slide: &SlideJson) -> Vec<ContentJson> {
+        let mut box_rect = Rect::new(0, 0, CONTENT_WIDTH, CONTENT_HEIGHT);
+        if let Some(slides) = &self.slides {
+            box_rect.width = slides.box_size.width;
+            box_rect.height = slides.box_size.height;
+        }
+        let mut items = vec![];
+        Self::flatten_content(&slide.content, box_rect, self.tab_index, &mut items);
+        if let Some(Zoom::Zoomed(index)) = self.zoom {
+            if let Some(item) = items.get_mut(index) {
+                item.rect = Some(Rect::new(
+                    1,
+                    1,
+                    box_rect.width.saturating_sub(2),
+                    box_rect.height.saturating_sub(2),
+                ));
+            }
+        }
+        items
+    }
+
+    fn flatten_content(
+        content: &[ContentJson],
+        area: Rect,
+        tab_index: usize,
+        items: &mut Vec<ContentJson>,
+    ) {
+        for item in content {
+            let mut item = item.clone();
+            item.rect = Some(match item.rect {
+                Some(r) => Rect::new(area.x + r.x, area.y + r.y, r.width, r.height),
+                None => area,
+            });
+            let tabs = item.tabs.clone().unwrap_or_default();
+            let item_rect = item.rect.unwrap_or(area);
+            items.push(item);
+
+            if let Some(tab) = tabs.get(tab_index % tabs.len().max(1)) {
+                let pane = Rect::new(
+                    item_rect.x,
+                    item_rect.y + TAB_BAR_HEIGHT,
+                    item_rect.width,
+                    item_rect.height.saturating_sub(TAB_BAR_HEIGHT),
+                );
+                Self::flatten_content(&tab.content, pane, tab_index, items);
+            }
+        }
+    }
+
+    /// Number of lines the item content can be scrolled by within its rect, code
+    /// blocks longer than their rect are always scrollable.
+    fn max_item_scroll(&self, index: usize, item: &ContentJson) -> u16 {
+        let is_code = item.type_ == SlideContentType::CodeHighlight;
+        if item.scrollable != Some(true) && !is_code {
+            return 0;
+        }
+        let rect = item.rect.unwrap_or_default();
+        let rect = if is_code {
+            self.code_area(item, rect)
+        } else {
+            rect
+        };
+        let mut height = rect.height;
+        if is_code && self.exec_runs.contains_key(&index) {
+            height -= exec_output_height(height);
+        }
+        let lines = if is_code {
+            let content = self.code_block(item).code;
+            // -- one column is left for scrollbar
+            let width = rect.width.saturating_sub(1);
+            let wrap = item.wrap == Some(true);
+            code_rows(&content, Self::code_gutter(item), width as usize, wrap) as u16
+        } else {
+            get_slide_content_string(item).lines().count() as u16
+        };
+        lines.saturating_sub(height)
+    }
+
+    /// Frame of code item drawn for `title`, `background` and `padding`, like the
+    /// window of editor screenshot.
+    fn code_chrome(&self, item: &ContentJson) -> Option<Block<'static>> {
+        if item.title.is_none() && item.background.is_none() && item.padding.is_none() {
+            return None;
+        }
+        let mut chrome = Block::default();
+        if let Some(title) = &item.title {
+            let title = match (title.is_empty(), &item.file) {
+                (true, Some(file)) => Path::new(file)
+                    .file_name()
+                    .map(|name| name.to_string_lossy().to_string())
+                    .unwrap_or_else(|| file.clone()),
+                _ => title.clone(),
+            };
+            chrome = chrome
+                .borders(Borders::ALL)
+                .border_set(theme().border.unwrap_or(symbols::border::ROUNDED))
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .title(Line::from(format!(" {} ", title)).bold());
+        }
+        let background = match item.background.as_deref() {
+            Some("theme") => self.highlights.background(&self.code_block(item)),
+            Some(color) => parse_color(color),
+            None => None,
+        };
+        if let Some(background) = background {
+            chrome = chrome.style(Style::default().bg(capabilities().color(background)));
+        }
+        if let Some(padding) = item.padding {
+            chrome = chrome.padding(Padding::symmetric(padding * 2, padding));
+        }
+        Some(chrome)
+    }
+
+    /// Part of `rect` left for code inside its frame, above the legend of callouts.
+    fn code_area(&self, item: &ContentJson, rect: Rect) -> Rect {
+        let mut area = self
+            .code_chrome(item)
+            .map(|chrome| chrome.inner(rect))
+            .unwrap_or(rect);
+        area.height -= Self::legend_height(item, area.height);
+        area
+    }
+
+    /// Rows of callout legend, it takes at most half of the code item.
+    fn legend_height(item: &ContentJson, height: u16) -> u16 {
+        let count = item.callouts.as_ref().map(|c| c.len()).unwrap_or(0);
+        (count as u16).min(height / 2)
+    }
+
+    /// Columns of line numbers of code item.
+    fn code_gutter(item: &ContentJson) -> usize {
+        if item.line_numbers != Some(true) {
+            return 0;
+        }
+        let count = get_slide_content_string(item).split('\n').count();
+        gutter_width(item.start_line.unwrap_or(1), count)
+    }
+
+    /// Number of columns code lines wider than the item rect can be scrolled by.
+    fn max_item_h_scroll(&self, index: usize, item: &ContentJson) -> u16 {
+        if item.type_ != SlideContentType::CodeHighlight || item.wrap == Some(true) {
+            return 0;
+        }
+        let mut width = self.code_area(item, item.rect.unwrap_or_default()).width;
+        if self.max_item_scroll(index, item) > 0 {
+            width = width.saturating_sub(1);
+        }
+        let code_width = code_width(&self.code_block(item).code, Self::code_gutter(item));
+        (code_width as u16).saturating_sub(width)
+    }
+
+    fn scroll_right(&mut self) {
+        let slide = self.get_slide();
+        let max = self
+            .flatten_items(&slide)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.max_item_h_scroll(index, item))
+            .max()
+            .unwrap_or(0);
+        self.h_scroll = (self.h_scroll + H_SCROLL_STEP).min(max);
+    }
+
+    fn scroll_left(&mut self) {
+        self.h_scroll = self.h_scroll.saturating_sub(H_SCROLL_STEP);
+    }
+
+    /// `‹` and `›` at the edges of code lines cut by the rect, so clipped code is not
+    /// taken for the whole listing.
+    fn draw_clip_markers(f: &mut Frame<'_>, lines: &[Line], scroll: (u16, u16), rect: Rect) {
+        let rect = rect.intersection(*f.buffer_mut().area());
+        if rect.is_empty() {
+            return;
+        }
+        let (top, left) = scroll;
+        let caps = capabilities();
+        let style = Style::default()
+            .fg(Color::Black)
+            .bg(Color::Rgb(100, 100, 100));
+        let rows = lines.iter().skip(top as usize).take(rect.height as usize);
+        for (y, line) in (rect.y..).zip(rows) {
+            let width = line.width() as u16;
+            if left > 0 && width > 0 {
+                f.buffer_mut()
+                    .get_mut(rect.x, y)
+                    .set_symbol(caps.glyph(UnicodeLevel::Basic, "‹", "<"))
+                    .set_style(style);
+            }
+            if width.saturating_sub(left) > rect.width && rect.width > 0 {
+                f.buffer_mut()
+                    .get_mut(rect.right() - 1, y)
+                    .set_symbol(caps.glyph(UnicodeLevel::Basic, "›", ">"))
+                    .set_style(style);
+            }
+        }
+    }
+
+    /// Options of the first `Menu` item on the slide.
+    fn menu_options(&self) -> Option<Vec<MenuOptionJson>> {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .into_iter()
+            .find(|item| item.type_ == SlideContentType::Menu && self.is_revealed(item))
+            .and_then(|item| item.options)
+    }
+
+    fn select_menu_option(&mut self, down: bool) {
+        let count = self.menu_options().map_or(0, |options| options.len());
+        self.menu_index = if down {
+            (self.menu_index + 1).min(count.saturating_sub(1))
+        } else {
+            self.menu_index.saturating_sub(1)
+        };
+    }
+
+    fn has_questions(&self) -> bool {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .iter()
+            .any(|i| i.type_ == SlideContentType::Questions)
+    }
+
+    fn toggle_answered(&mut self) {
+        if !self.has_questions() {
+            return;
+        }
+        if let Err(e) = notes::toggle_answered(&self.json_slides, self.question_index) {
+            log::error!("Failed to update notes: {e:?}");
+        }
+        self.load_questions();
+    }
+
+    fn load_questions(&mut self) {
+        self.questions = read_notes(&self.json_slides);
+    }
+
+    fn scroll_down(&mut self) {
+        if self.has_questions() {
+            let count = self.questions.len();
+            self.question_index = (self.question_index + 1).min(count.saturating_sub(1));
+            return;
+        }
+        let slide = self.get_slide();
+        let max = self
+            .flatten_items(&slide)
+            .iter()
+            .enumerate()
+            .map(|(index, item)| self.max_item_scroll(index, item))
+            .max()
+            .unwrap_or(0);
+        self.scroll = (self.scroll + 1).min(max);
+    }
+
+    fn scroll_up(&mut self) {
+        self.question_index = self.question_index.saturating_sub(1);
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    /// Widget is drawn next to scrollbar when it can be scrolled, area of the widget is
+    /// returned.
+    fn render_scrollable<W: Widget>(
+        &self,
+        f: &mut Frame<'_>,
+        widget: W,
+        max: u16,
+        rect: Rect,
+    ) -> Rect {
+        let mut text_rect = rect;
+        if max > 0 {
+            text_rect.width = text_rect.width.saturating_sub(1);
+            let mut state =
+                ScrollbarState::new(max as usize).position(self.scroll.min(max) as usize);
+            let caps = capabilities();
+            let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+                .begin_symbol(None)
+                .end_symbol(None)
+                .track_symbol(Some(caps.glyph(UnicodeLevel::Basic, "│", "|")))
+                .thumb_symbol(caps.glyph(UnicodeLevel::Basic, "█", "#"))
+                .style(Style::default().fg(Color::Rgb(100, 100, 100)));
+            f.render_stateful_widget(scrollbar, rect, &mut state);
+        }
+        f.render_widget(widget, text_rect);
+        text_rect
+    }
+
+    fn is_revealed(&self, item: &ContentJson) -> bool {
+        let delay = Duration::from_millis(item.delay.unwrap_or(0));
+        self.reveal_all || self.slide_start.elapsed() >= delay
+    }
+
+    /// Characters or lines of code item typed so far, `None` once it is shown whole.
+    fn typed_units(&self, item: &ContentJson) -> Option<usize> {
+        let typewriter = item.typewriter.as_ref()?;
+        if self.reveal_all {
+            return None;
+        }
+        let step = item.typewriter_ms.unwrap_or(match typewriter {
+            Typewriter::Chars => DEFAULT_TYPEWRITER_CHAR_MS,
+            Typewriter::Lines => DEFAULT_TYPEWRITER_LINE_MS,
+        });
+        let delay = Duration::from_millis(item.delay.unwrap_or(0));
+        let elapsed = self.slide_start.elapsed().saturating_sub(delay);
+        let units = (elapsed.as_millis() / step.max(1) as u128) as usize;
+        let total = typewriter_units(&self.code_block(item).code, typewriter);
+        (units < total).then_some(units)
+    }
+
+    /// Lines of code item with `steps` shown at current step, `None` once it is shown
+    /// whole.
+    fn step_lines(&self, item: &ContentJson) -> Option<usize> {
+        let steps = item.steps.as_ref()?;
+        let count = self.code_block(item).code.split('\n').count();
+        let counts = step_line_counts(steps, item.start_line.unwrap_or(1), count);
+        counts
+            .get(self.code_step)
+            .copied()
+            .filter(|lines| *lines < count)
+    }
+
+    /// Code items with `steps` have lines left to reveal on current slide.
+    fn has_pending_steps(&self) -> bool {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .iter()
+            .any(|i| self.is_revealed(i) && self.step_lines(i).is_some())
+    }
+
+    /// Items with entrance delay are still waiting to appear (or being typed) on
+    /// current slide.
+    fn has_pending_items(&self) -> bool {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .iter()
+            .any(|i| !self.is_revealed(i) || self.typed_units(i).is_some())
+    }
+
+    fn enter_pending_slide(&mut self) {
+        if self.needs_enter {
+            self.needs_enter = false;
+            self.start_slide();
+            self.store_images();
+            self.start_terminals();
+        }
+        self.receive_images();
+    }
+
+    /// Serve state to presenter views, or follow the presenting instance in one.
+    fn start_presenter(&mut self) {
+        let path = socket_path(&self.json_slides);
+        if self.presenter_view {
+            self.presenter_follower = Some(PresenterFollower::connect(path));
+            return;
+        }
+        match PresenterServer::bind(&path, self.presenter_state()) {
+            Ok(server) => self.presenter_server = Some(server),
+            Err(e) => log::error!("Presenter view is not available: {}", e.report()),
+        }
+    }
+
+    fn presenter_state(&self) -> PresenterState {
+        PresenterState {
+            slide: self.slide_index,
+            started: self
+                .talk_elapsed()
+                .map(|elapsed| unix_now().saturating_sub(elapsed.as_secs())),
+        }
+    }
+
+    fn talk_elapsed(&self) -> Option<Duration> {
+        self.talk_started.map(|started| started.elapsed())
+    }
+
+    fn reset_talk_clock(&mut self) {
+        self.talk_started = Some(Instant::now());
+        if let Some(server) = &self.presenter_server {
+            server.send(self.presenter_state());
+        }
+    }
+
+    /// Presenter view goes where the presenting instance went.
+    fn follow_presenter(&mut self) {
+        let received = self.presenter_follower.as_ref().and_then(|f| f.state());
+        if let Some(received) = received {
+            self.talk_started = received.started.and_then(|started| {
+                Instant::now().checked_sub(Duration::from_secs(unix_now().saturating_sub(started)))
+            });
+            if self.followed_slide != Some(received.slide) {
+                self.followed_slide = Some(received.slide);
+                self.show_slide(received.slide);
+            }
+        }
+    }
+
+    /// Restart entrance delays and slide audio, called when a slide is entered.
+    fn start_slide(&mut self) {
+        self.slide_start = Instant::now();
+        self.reveal_all = false;
+        self.code_step = 0;
+        self.menu_index = 0;
+        self.zoom = match self.zoom {
+            Some(Zoom::Selecting(_)) => Some(Zoom::Selecting(0)),
+            _ => None,
+        };
+        self.advance_requested = None;
+        self.autoplay_since = Instant::now();
+        self.exec_runs.clear();
+        self.load_questions();
+        if let Some(rehearsal) = &mut self.rehearsal {
+            rehearsal.enter(self.slide_index);
+        }
+
+        if let Some(server) = &self.presenter_server {
+            server.send(self.presenter_state());
+        }
+        if self.presenter_view {
+            return;
+        }
+        self.audio.stop();
+        if let Some(audio) = self.get_slide().audio {
+            if let Err(e) = self.audio.play(&deck_dir(&self.json_slides).join(audio)) {
+                log::error!("Failed to play slide audio: {e:?}");
+            }
+        }
+        self.announce_slide();
+        if let Some(tx) = &self.action_tx {
+            let _ = tx.send(Action::SlideChanged(self.slide_index, self.slide_count));
+            let keys = self.slide_keys();
+            let _ = tx.send(Action::SlideKeys(keys.into_iter().collect()));
+        }
+    }
+
+    /// Key bindings of the deck and of the current slide, which wins over the deck.
+    fn slide_keys(&self) -> BTreeMap<String, Action> {
+        let mut keys = self
+            .slides
+            .as_ref()
+            .and_then(|slides| slides.keys.clone())
+            .unwrap_or_default();
+        keys.extend(self.get_slide().keys.unwrap_or_default());
+        keys
+    }
+
+    /// Tell the terminal where the talk is, for window managers, recorders and tmux.
+    fn announce_slide(&self) {
+        let slide = self.get_slide();
+        let position = format!(
+            "{}/{}",
+            self.slide_number(self.slide_index),
+            self.slide_total()
+        );
+        let text = match &slide.title {
+            Some(title) => format!("{} {}", position, title),
+            None => position,
+        };
+        if self.terminal.title {
+            if let Err(e) = osc::set_title(&format!("tui-slides {}", text)) {
+                log::error!("Failed to set terminal title: {e:?}");
+            }
+        }
+        if self.terminal.notify {
+            if let Err(e) = osc::notify(&text) {
+                log::error!("Failed to send terminal notification: {e:?}");
+            }
+        }
+    }
+
+    fn next_tab(&mut self) {
+        self.tab_index = self.tab_index.wrapping_add(1);
+        self.scroll = 0;
+        self.h_scroll = 0;
+        self.code_step = 0;
+        self.exec_runs.clear();
+        self.store_images();
+        self.start_terminals();
+    }
+
+    /// Run visible code blocks with `exec`, runs of previous press are killed.
+    fn run_code(&mut self) {
+        let slide = self.get_slide();
+        for (index, item) in self.flatten_items(&slide).iter().enumerate() {
+            if !self.is_revealed(item) {
+                continue;
+            }
+            if let Some(run) = self.start_run(item) {
+                self.exec_runs.insert(index, run);
+            }
+        }
+    }
+
+    /// Run code blocks with `refresh_secs` once shown and again whenever the last run
+    /// is done and older than that, so live output keeps changing during the demo.
+    fn refresh_code(&mut self) {
+        let slide = self.get_slide();
+        for (index, item) in self.flatten_items(&slide).iter().enumerate() {
+            let Some(refresh) = item.refresh_secs else {
+                continue;
+            };
+            if !self.is_revealed(item) {
+                continue;
+            }
+            let run = match self.exec_runs.get(&index) {
+                Some(run) if !run.is_done() => continue,
+                Some(run) if run.started().elapsed() < Duration::from_secs(refresh) => continue,
+                Some(run) => self.start_run(item).map(|new| new.refreshing(run)),
+                None => self.start_run(item),
+            };
+            if let Some(run) = run {
+                self.exec_runs.insert(index, run);
+            }
+        }
+    }
+
+    fn start_run(&self, item: &ContentJson) -> Option<ExecRun> {
+        let exec = item.exec.as_ref()?;
+        let code = get_slide_content_string(item);
+        let language = item
+            .language
+            .clone()
+            .or_else(|| detect_language(&code, item.file.as_deref()));
+        let language = language.as_deref();
+        let Some(interpreter) = exec::interpreter(exec, language, &self.exec.interpreters) else {
+            log::error!("No interpreter configured for {:?}", language);
+            return None;
+        };
+        let cwd = deck_dir(&self.json_slides);
+        Some(exec::run(&code, language, &interpreter, cwd))
+    }
+
+    /// Copy first visible code block of the slide to clipboard.
+    fn copy_code(&self) {
+        let slide = self.get_slide();
+        let Some(item) = self
+            .flatten_items(&slide)
+            .into_iter()
+            .find(|i| i.type_ == SlideContentType::CodeHighlight && self.is_revealed(i))
+        else {
+            return;
+        };
+        if let Err(e) = osc::copy(&self.code_block(&item).code) {
+            log::error!("Failed to copy code: {e:?}");
+        }
+    }
+
+    /// Output of code block run in bordered pane, stderr is shown in red.
+    fn draw_exec_output(f: &mut Frame<'_>, run: &ExecRun, rect: Rect) {
+        let (title, lines) = match run.state() {
+            ExecState::Running => (" running… ".to_string(), vec![]),
+            ExecState::Done {
+                stdout,
+                stderr,
+                status,
+            } => {
+                let title = match status {
+                    Some(0) => " output ".to_string(),
+                    Some(code) => format!(" exit {} ", code),
+                    None => " killed ".to_string(),
+                };
+                let mut lines: Vec<Line> =
+                    stdout.lines().map(|l| Line::from(l.to_string())).collect();
+                lines.extend(stderr.lines().map(|l| Line::from(l.to_string()).red()));
+                (title, lines)
+            }
+            ExecState::Failed(e) => (" error ".to_string(), vec![Line::from(e).red()]),
+        };
+        let block = Block::default()
+            .borders(Borders::TOP)
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(title);
+        let height = rect.height.saturating_sub(2);
+        let scroll = (lines.len() as u16).saturating_sub(height);
+        f.render_widget(Paragraph::new(lines).block(block).scroll((scroll, 0)), rect);
+    }
+
+    fn section(&self, index: usize) -> Option<(usize, usize)> {
+        self.slides.as_ref()?.slides.get(index)?.section
+    }
+
+    /// Number of slide as shown to the audience, `3.2` for sub-slides.
+    fn slide_number(&self, index: usize) -> String {
+        match self.section(index) {
+            Some((section, 0)) => section.to_string(),
+            Some((section, sub)) => format!("{}.{}", section, sub),
+            None => (index + 1).to_string(),
+        }
+    }
+
+    /// Slides, or sections in decks with sub-slides.
+    fn slide_total(&self) -> usize {
+        match self.section(self.slide_count.saturating_sub(1)) {
+            Some((section, _)) => section,
+            None => self.slide_count,
+        }
+    }
+
+    fn deck_slides(&self) -> &[SlideJson] {
+        self.slides
+            .as_ref()
+            .map(|slides| slides.slides.as_slice())
+            .unwrap_or_default()
+    }
+
+    /// Show where navigation leads, nothing changes when it leads nowhere.
+    fn take_step(&mut self, step: Option<Step>) {
+        match step {
+            Some(Step::Slide(index)) => self.show_slide(index),
+            Some(Step::End) => self.at_end = true,
+            None => {}
+        }
+    }
+
+    fn down_slide(&mut self) {
+        let step = navigation::down_slide(self.deck_slides(), self.slide_index, &self.navigation);
+        self.take_step(step);
+    }
+
+    fn up_slide(&mut self) {
+        if self.at_end {
+            self.at_end = false;
+            return;
+        }
+        let above =
+            navigation::up_slide(self.deck_slides(), self.slide_index, self.navigation.wrap);
+        self.take_step(above.map(Step::Slide));
+    }
+
+    /// Part of the flow of slides shown, for the progress bar.
+    fn deck_progress(&self) -> f64 {
+        let Some(slides) = &self.slides else {
+            return 0.0;
+        };
+        let flow = flow_slides(&slides.slides);
+        let budgets: Vec<Option<u64>> = flow
+            .iter()
+            .map(|&index| slides.slides[index].duration_secs)
+            .collect();
+        let shown = flow
+            .iter()
+            .filter(|&&index| index <= self.slide_index)
+            .count();
+        deck_progress(&budgets, shown, self.progress.weighted)
+    }
+
+    /// First key stepping off slide with `confirm_advance` only asks for another.
+    fn advance_unconfirmed(&mut self) -> bool {
+        if self.get_slide().confirm_advance != Some(true) {
+            return false;
+        }
+        if self.advance_pending() {
+            self.advance_requested = None;
+            return false;
+        }
+        self.advance_requested = Some(Instant::now());
+        true
+    }
+
+    fn advance_pending(&self) -> bool {
+        self.advance_requested
+            .is_some_and(|requested| requested.elapsed() < ADVANCE_CONFIRM_TIMEOUT)
+    }
+
+    fn next_slide(&mut self) {
+        let step = navigation::next_slide(self.deck_slides(), self.slide_index, &self.navigation);
+        self.take_step(step);
+    }
+
+    fn previous_slide(&mut self) {
+        if self.at_end {
+            self.at_end = false;
+            return;
+        }
+        let previous =
+            navigation::previous_slide(self.deck_slides(), self.slide_index, self.navigation.wrap);
+        self.take_step(previous.map(Step::Slide));
+    }
+
+    /// Jump to slide, the current one is kept in history.
+    fn go_to_slide(&mut self, index: usize) {
+        if let Some(index) = jump(&mut self.history, self.slide_count, self.slide_index, index) {
+            self.show_slide(index);
+        }
+    }
+
+    fn show_slide(&mut self, index: usize) {
+        self.at_end = false;
+        if index == self.slide_index || index >= self.slide_count {
+            return;
+        }
+        if self.talk_started.is_none() && !self.presenter_view {
+            self.reset_talk_clock();
+        }
+        self.slide_index = index;
+        self.tab_index = 0;
+        self.scroll = 0;
+        self.h_scroll = 0;
+
+        self.needs_enter = true;
+    }
+
+    fn nav_hud_visible(&self) -> bool {
+        self.nav_hud_until
+            .is_some_and(|until| Instant::now() < until)
+    }
+
+    /// Drawing mode, pressing the left button starts stroke and dragging draws it.
+    fn handle_draw_mouse(&mut self, mouse: MouseEvent) {
+        let annotation = self.annotations.entry(self.slide_index).or_default();
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                annotation.begin(DRAW_COLORS[self.draw_color], mouse.column, mouse.row);
+            }
+            MouseEventKind::Drag(MouseButton::Left) => {
+                annotation.extend(mouse.column, mouse.row);
+            }
+            _ => {}
+        }
+    }
+
+    /// Color of drawing mode and its keys in the top right corner of the slide.
+    fn draw_drawing_hint(&self, f: &mut Frame<'_>, content: Rect) {
+        if !self.drawing {
+            return;
+        }
+        let hint = Line::from(vec![
+            " DRAW ".black().on_white(),
+            " ● ".fg(DRAW_COLORS[self.draw_color]).on_black(),
+            " D color  u undo  U clear  d done ".dark_gray().on_black(),
+        ]);
+        let width = (hint.width() as u16).min(content.width);
+        let rect = Rect::new(content.right().saturating_sub(width), content.y, width, 1);
+        f.render_widget(hint, rect.intersection(f.size()));
+    }
+
+    /// Pointer follows the mouse moved with Alt or Ctrl held, once shown it follows it
+    /// until hidden with Esc.
+    fn handle_pointer_mouse(&mut self, mouse: MouseEvent) {
+        let held = mouse
+            .modifiers
+            .intersects(KeyModifiers::ALT | KeyModifiers::CONTROL);
+        if matches!(mouse.kind, MouseEventKind::Moved | MouseEventKind::Drag(_))
+            && (held || self.pointer.is_some())
+        {
+            self.pointer = Some((mouse.column, mouse.row));
+        }
+    }
+
+    /// Navigation strip at the bottom edge, hovered marker shows slide title and
+    /// click jumps to it.
+    fn handle_nav_hud_mouse(&mut self, mouse: MouseEvent) {
+        let area = self.area;
+        let at_bottom = mouse.row >= area.bottom().saturating_sub(NAV_HUD_HEIGHT);
+        let marker = marker_at(
+            mouse.column.saturating_sub(area.x),
+            area.width,
+            self.slide_count,
+        );
+        match mouse.kind {
+            MouseEventKind::Moved if at_bottom => {
+                self.nav_hud_until = Some(Instant::now() + NAV_HUD_TIMEOUT);
+                self.nav_hud_hovered = marker;
+            }
+            MouseEventKind::Moved => {
+                self.nav_hud_hovered = None;
+            }
+            MouseEventKind::Down(MouseButton::Left) if at_bottom && self.nav_hud_visible() => {
+                if let Some(index) = marker {
+                    self.go_to_slide(index);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Item under mouse which can be scrolled.
+    fn scrollable_at(&self, column: u16, row: u16) -> bool {
+        let slide = self.get_slide();
+        let cell = Rect::new(column, row, 1, 1);
+        self.flatten_items(&slide)
+            .iter()
+            .enumerate()
+            .any(|(index, item)| {
+                self.get_slide_rect(self.content_area, item.rect)
+                    .intersects(cell)
+                    && self.max_item_scroll(index, item) > 0
+            })
+    }
+
+    /// Wheel over scrollable item scrolls it, elsewhere it goes to the next or the
+    /// previous slide.
+    fn wheel_action(&mut self, mouse: MouseEvent, down: bool) -> Option<Action> {
+        if self.scrollable_at(mouse.column, mouse.row) {
+            return Some(if down {
+                Action::ScrollDown
+            } else {
+                Action::ScrollUp
+            });
+        }
+        let now = Instant::now();
+        if self
+            .wheel_navigated
+            .is_some_and(|last| now.duration_since(last) < WHEEL_NAV_INTERVAL)
+        {
+            return None;
+        }
+        self.wheel_navigated = Some(now);
+        Some(if down { Action::Next } else { Action::Previous })
+    }
+
+    /// Go to slide named `id`, unknown names are logged.
+    fn go_to_id(&mut self, id: &str) {
+        let index = self
+            .slides
+            .as_ref()
+            .and_then(|slides| slide_with_id(&slides.slides, id));
+        match index {
+            Some(index) => self.go_to_slide(index),
+            None => log::error!("No slide with id '{id}'"),
+        }
+    }
+
+    /// `goto` of the item under mouse, or of the first item with it without position.
+    fn link_at(&self, position: Option<(u16, u16)>) -> Option<String> {
+        let slide = self.get_slide();
+        self.flatten_items(&slide)
+            .into_iter()
+            .filter(|item| item.goto.is_some() && self.is_revealed(item))
+            .find(|item| {
+                position.is_none_or(|(column, row)| {
+                    self.get_slide_rect(self.content_area, item.rect)
+                        .intersects(Rect::new(column, row, 1, 1))
+                })
+            })
+            .and_then(|item| item.goto)
+    }
+
+    /// Click on the slide counter opens goto prompt, other left clicks go to the
+    /// previous slide on the left half and the next one on the right half, right click
+    /// goes back.
+    fn handle_click_mouse(&mut self, mouse: MouseEvent) -> Option<Action> {
+        let focused = self.pty_focus || self.overview.is_some() || self.prompt.is_some();
+        let at_hud = mouse.row >= self.area.bottom().saturating_sub(NAV_HUD_HEIGHT)
+            && self.nav_hud_visible();
+        if focused || at_hud {
+            return None;
+        }
+        let content = self.content_area;
+        let counter_width = (self.slide_number(self.slide_index).len()
+            + self.slide_total().to_string().len()
+            + 4) as u16;
+        let on_counter = mouse.row == content.bottom().saturating_sub(1)
+            && mouse.column >= content.right().saturating_sub(counter_width)
+            && mouse.column < content.right();
+        let link = match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) => {
+                self.link_at(Some((mouse.column, mouse.row)))
+            }
+            _ => None,
+        };
+        match mouse.kind {
+            MouseEventKind::Down(MouseButton::Left) if on_counter => {
+                Some(Action::GotoPrompt(String::new()))
+            }
+            MouseEventKind::Down(MouseButton::Left) if link.is_some() => link.map(Action::GotoId),
+            MouseEventKind::Down(MouseButton::Left)
+                if mouse.column < self.area.x + self.area.width / 2 =>
+            {
+                Some(Action::Previous)
+            }
+            MouseEventKind::Down(MouseButton::Left) => Some(Action::Next),
+            MouseEventKind::Down(MouseButton::Right) => Some(Action::Previous),
+            MouseEventKind::ScrollDown => self.wheel_action(mouse, true),
+            MouseEventKind::ScrollUp => self.wheel_action(mouse, false),
+            _ => None,
+        }
+    }
+
+    fn handle_bookmark(&mut self, key: BookmarkKey, letter: char) {
+        if !letter.is_alphanumeric() {
+            return;
+        }
+        match key {
+            BookmarkKey::Set => {
+                if let Err(e) = set_bookmark(&self.json_slides, letter, self.slide_index + 1) {
+                    log::error!("Failed to save bookmark: {e:?}");
+                }
+            }
+            BookmarkKey::Jump => match read_bookmarks(&self.json_slides).get(&letter) {
+                Some(&slide) => self.go_to_slide(slide.saturating_sub(1)),
+                None => log::info!("No slide marked with '{letter}'"),
+            },
+        }
+    }
+
+    fn toggle_overview(&mut self) -> Option<Action> {
+        if self.overview.take().is_some() {
+            return Some(Action::Mode(Mode::Home));
+        }
+        self.overview = Some(self.slide_index);
+        Some(Action::Mode(Mode::Input))
+    }
+
+    /// Arrows (or `hjkl`) move the selection in overview grid, Enter goes to it.
+    fn handle_overview_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let selected = self.overview?;
+        let columns = grid_columns(self.area.width);
+        let last = self.slide_count.saturating_sub(1);
+        let selected = match key.code {
+            KeyCode::Left | KeyCode::Char('h') => selected.saturating_sub(1),
+            KeyCode::Right | KeyCode::Char('l') => (selected + 1).min(last),
+            KeyCode::Up | KeyCode::Char('k') => selected.saturating_sub(columns),
+            KeyCode::Down | KeyCode::Char('j') => (selected + columns).min(last),
+            KeyCode::Home => 0,
+            KeyCode::End => last,
+            KeyCode::Enter => {
+                self.overview = None;
+                self.go_to_slide(selected);
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Esc | KeyCode::Char('o' | 'q') => return self.toggle_overview(),
+            _ => selected,
+        };
+        self.overview = Some(selected);
+        None
+    }
+
+    fn draw_overview(&self, f: &mut Frame<'_>, area: Rect) {
+        let (Some(selected), Some(slides)) = (self.overview, &self.slides) else {
+            return;
+        };
+        let previews: Vec<SlidePreview> = slides
+            .slides
+            .iter()
+            .map(|slide| SlidePreview {
+                title: slide.title.clone(),
+                text: slide
+                    .content
+                    .first()
+                    .map(get_slide_content_string)
+                    .unwrap_or_default(),
+            })
+            .collect();
+        let overview = Overview::new(&previews, selected, self.slide_index);
+        f.render_widget(overview, area);
+    }
+
+    /// Slides with title matching the palette query, best first.
+    fn palette_matches(&self, query: &str) -> Vec<usize> {
+        self.slides
+            .as_ref()
+            .map(|slides| fuzzy_titles(&slides.slides, query))
+            .unwrap_or_default()
+    }
+
+    fn handle_palette_key(&mut self, key: KeyEvent) -> Option<Action> {
+        let (query, selected) = self.palette.as_mut()?;
+        match key.code {
+            KeyCode::Esc => {
+                self.palette = None;
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Char('p' | 'c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.palette = None;
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Enter => {
+                let query = query.clone();
+                let selected = *selected;
+                self.palette = None;
+                if let Some(&index) = self.palette_matches(&query).get(selected) {
+                    self.go_to_slide(index);
+                }
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Up => *selected = selected.saturating_sub(1),
+            KeyCode::Down => *selected += 1,
+            KeyCode::Backspace => {
+                query.pop();
+                *selected = 0;
+            }
+            KeyCode::Char(c) => {
+                query.push(c);
+                *selected = 0;
+            }
+            _ => {}
+        }
+        let query = query.clone();
+        let count = self.palette_matches(&query).len();
+        if let Some((_, selected)) = &mut self.palette {
+            *selected = (*selected).min(count.saturating_sub(1));
+        }
+        None
+    }
+
+    /// Zoom selection, arrows pick item of the slide and Enter zooms it.
+    fn handle_zoom_key(&mut self, selected: usize, key: KeyEvent) -> Option<Action> {
+        let count = self.flatten_items(&self.get_slide()).len().max(1);
+        let selected = match key.code {
+            KeyCode::Esc | KeyCode::Char('z') => {
+                self.zoom = None;
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Enter => {
+                self.zoom = Some(Zoom::Zoomed(selected));
+                self.scroll = 0;
+                self.h_scroll = 0;
+                return Some(Action::Mode(Mode::Home));
+            }
+            KeyCode::Right | KeyCode::Down | KeyCode::Tab | KeyCode::Char('l' | 'j') => {
+                (selected + 1) % count
+            }
+            KeyCode::Left | KeyCode::Up | KeyCode::BackTab | KeyCode::Char('h' | 'k') => {
+                (selected + count - 1) % count
+            }
+            _ => selected,
+        };
+        self.zoom = Some(Zoom::Selecting(selected));
+        None
+    }
+
+    /// Frame of item picked for zoom, or hint how to leave zoom.
+    fn draw_zoom(&self, f: &mut Frame<'_>, content: Rect) {
+        let (hint, selected) = match self.zoom {
+            Some(Zoom::Selecting(selected)) => {
+                (" ZOOM ←/→ item  Enter zoom  Esc cancel ", selected)
+            }
+            Some(Zoom::Zoomed(_)) => (" ZOOM z or Esc back ", usize::MAX),
+            None => return,
+        };
+        let items = self.flatten_items(&self.get_slide());
+        if let Some(item) = items.get(selected) {
+            let rect = self
+                .get_slide_rect(content, item.rect)
+                .intersection(f.size());
+            f.render_widget(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_type(BorderType::Thick)
+                    .border_style(Style::default().fg(Color::Yellow)),
+                rect,
+            );
+        }
+        let width = (hint.chars().count() as u16).min(content.width);
+        let rect = Rect::new(content.right().saturating_sub(width), content.y, width, 1);
+        f.render_widget(hint.black().on_yellow(), rect.intersection(f.size()));
+    }
+
+    fn draw_palette(&self, f: &mut Frame<'_>, area: Rect) {
+        let (Some((query, selected)), Some(slides)) = (&self.palette, &self.slides) else {
+            return;
+        };
+        let matches: Vec<(usize, String)> = self
+            .palette_matches(query)
+            .into_iter()
+            .map(|index| {
+                let title = slides.slides[index].title.clone();
+                (index, title.unwrap_or_default())
+            })
+            .collect();
+        f.render_widget(Palette::new(query, &matches, *selected), area);
+    }
+
+    fn draw_toc(&self, f: &mut Frame<'_>, layout: &SlidesLayout) {
+        let (true, Some(slides)) = (self.show_toc, &self.slides) else {
+            return;
+        };
+        let titles: Vec<Option<String>> = slides.slides.iter().map(|s| s.title.clone()).collect();
+        let toc = Toc::new(&titles, self.slide_index);
+        f.render_widget(toc, get_toc_layout(layout).intersection(f.size()));
+    }
+
+    /// Close the topmost overlay only, so one Esc does not drop everything shown.
+    fn cancel(&mut self) {
+        if self.show_help {
+            self.show_help = false;
+        } else if self.zoom.is_some() {
+            self.zoom = None;
+        } else if self.drawing {
+            self.drawing = false;
+        } else if self.pointer.is_some() {
+            self.pointer = None;
+        } else if self.reload_error.is_some() {
+            self.reload_error = None;
+        } else {
+            self.search_matches.clear();
+        }
+    }
+
+    fn draw_help(&self, f: &mut Frame<'_>, area: Rect) {
+        if !self.show_help {
+            return;
+        }
+        let mut bindings = self.keybindings.clone();
+        for (key, action) in self.slide_keys() {
+            match parse_key_sequence(&key) {
+                Ok(keys) => bindings.insert(keys, action),
+                Err(_) => continue,
+            };
+        }
+        let actions = key_help(&bindings);
+        f.render_widget(Help::new(&actions), area);
+    }
+
+    /// Seconds ahead of `duration_secs` of slides, hidden slides have no budget.
+    fn pace(&self) -> Option<i64> {
+        let slides = self.slides.as_ref()?;
+        let budgets: Vec<Option<u64>> = slides
+            .slides
+            .iter()
+            .map(|slide| slide.duration_secs.filter(|_| slide.hidden != Some(true)))
+            .collect();
+        pace(&budgets, self.slide_index, self.talk_elapsed()?)
+    }
+
+    /// Presenter view, speaker notes of the slide next to text of the next one below
+    /// position and time of the talk.
+    fn draw_presenter(&self, f: &mut Frame<'_>, area: Rect) {
+        f.render_widget(Clear, area);
+        let connected = self
+            .presenter_follower
+            .as_ref()
+            .and_then(|follower| follower.state())
+            .is_some();
+        let mut status = if connected {
+            let elapsed = self
+                .talk_elapsed()
+                .map_or("not started".to_string(), format_duration);
+            let text = format!(
+                " Slide {}/{}   {} ",
+                self.slide_number(self.slide_index),
+                self.slide_total(),
+                elapsed
+            );
+            Line::from(text.black().on_green())
+        } else {
+            Line::from(
+                format!(" Waiting for tui-slides showing {} ", self.json_slides)
+                    .black()
+                    .on_green(),
+            )
+        };
+        if let (true, Some(pace)) = (connected, self.pace()) {
+            let text = format!(
+                " {}{} ",
+                if pace < 0 { "-" } else { "+" },
+                format_duration(Duration::from_secs(pace.unsigned_abs()))
+            );
+            status.spans.push(if pace < 0 {
+                text.white().on_red()
+            } else {
+                text.black().on_cyan()
+            });
+        }
+        let rows = Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).split(area);
+        let columns = Layout::horizontal([Constraint::Percentage(60), Constraint::Percentage(40)])
+            .split(rows[1]);
+        f.render_widget(Paragraph::new(status), rows[0]);
+
+        let block = |title: String| {
+            Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+                .title(title)
+        };
+        let slide = self.get_slide();
+        let title = slide.title.clone().unwrap_or_default();
+        let notes = slide.notes.unwrap_or_default();
+        f.render_widget(
+            Paragraph::new(notes)
+                .wrap(Wrap { trim: false })
+                .block(block(format!(" Notes: {} ", title))),
+            columns[0],
+        );
+
+        let next = self.next_flow_slide();
+        let (next_title, next_text) = match next.and_then(|i| self.slides.as_ref()?.slides.get(i)) {
+            Some(next) => (
+                format!(" Next: {} ", next.title.clone().unwrap_or_default()),
+                slide_text(next),
+            ),
+            None => (" Next: end of slides ".to_string(), String::new()),
+        };
+        let next_block = block(next_title);
+        let inner = next_block.inner(columns[1]);
+        f.render_widget(next_block, columns[1]);
+        let mut text_rect = inner;
+        if let Some(canvas) = next.and_then(|i| self.slide_canvas(i)) {
+            let (width, height) = miniature_size(canvas.area, inner.width, inner.height / 2);
+            let rect = Rect::new(inner.x + (inner.width - width) / 2, inner.y, width, height);
+            f.render_widget(Miniature::new(&canvas), rect);
+            text_rect.y += height + 1;
+            text_rect.height = text_rect.height.saturating_sub(height + 1);
+        }
+        f.render_widget(
+            Paragraph::new(next_text)
+                .dark_gray()
+                .wrap(Wrap { trim: true }),
+            text_rect,
+        );
+    }
+
+    /// Slide next of the current one shows, `None` on the last.
+    fn next_flow_slide(&self) -> Option<usize> {
+        flow_slides(self.deck_slides())
+            .into_iter()
+            .find(|&i| i > self.slide_index)
+    }
 
-impl Default for Slides {
-    fn default() -> Self {
-        Self::new()
+    /// Slide `index` drawn into buffer of the slide box, without the state of the
+    /// current slide: items revealed, nothing scrolled, images and terminals as
+    /// placeholders.
+    fn slide_canvas(&self, index: usize) -> Option<Buffer> {
+        let slides = self.slides.as_ref()?;
+        let slide = slides.slides.get(index)?;
+        let content = Rect::new(0, 0, slides.box_size.width, slides.box_size.height);
+        let mut buf = Buffer::empty(content);
+        let title_rect = Rect::new(0, 2, content.width, content.height.saturating_sub(2));
+        Self::make_title(slide).render(title_rect, &mut buf);
+        Self::make_block(None).render(content, &mut buf);
+        let placeholder = |name: &str, rect: Rect, buf: &mut Buffer| {
+            Paragraph::new(name.to_string())
+                .dark_gray()
+                .alignment(Alignment::Center)
+                .block(Block::default().borders(Borders::ALL).dark_gray())
+                .render(rect, buf);
+        };
+        let deadline = Instant::now() + Duration::from_millis(self.render.timeout_ms);
+        for (widget, item) in self.make_slide_items(slide) {
+            let rect = self
+                .get_slide_rect(content, item.rect)
+                .intersection(content);
+            let data = item.data.clone().unwrap_or_default();
+            match widget {
+                None | Some(ReturnSlideWidget::Image(_)) => placeholder("image", rect, &mut buf),
+                Some(ReturnSlideWidget::Terminal) => placeholder("terminal", rect, &mut buf),
+                Some(ReturnSlideWidget::Paragraph(p)) => p.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Line(l)) => l.render(rect, &mut buf),
+                Some(ReturnSlideWidget::BigText(b)) => b.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Block(b)) => b.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Sparkline(s)) => s.data(&data).render(rect, &mut buf),
+                Some(ReturnSlideWidget::CodeHighlight(_)) => {
+                    Paragraph::new(self.code_lines(&item, deadline)).render(rect, &mut buf)
+                }
+                Some(ReturnSlideWidget::Pie(p)) => p.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Table(t)) => Widget::render(t, rect, &mut buf),
+                Some(ReturnSlideWidget::Menu) => {
+                    let options = item.options.clone().unwrap_or_default();
+                    MenuWidget::new(&options, 0).render(rect, &mut buf);
+                }
+                Some(ReturnSlideWidget::Questions) => {
+                    QuestionList::new(&self.questions, 0).render(rect, &mut buf)
+                }
+                Some(ReturnSlideWidget::QrCode(q)) => q.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Icon(i)) => i.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Plot(p)) => p.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Rule(r)) => r.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Clock(c)) => c.render(rect, &mut buf),
+                Some(ReturnSlideWidget::Countdown(c)) => {
+                    c.elapsed(Duration::ZERO).render(rect, &mut buf)
+                }
+                Some(ReturnSlideWidget::Tabs(t)) => {
+                    let bar_rect = Rect::new(rect.x, rect.y, rect.width, 1.min(rect.height));
+                    t.select(0).render(bar_rect, &mut buf);
+                }
+            }
+        }
+        Some(buf)
     }
-}
 
-impl Slides {
-    pub fn new() -> Self {
-        Self {
-            action_tx: None,
-            json_slides: String::from(""),
-            slides: None,
-            slide_index: 0,
-            slide_count: 0,
-            picker: Picker::from_termios().unwrap(),
-            images: vec![],
+    /// Miniature of the next slide in the bottom right corner.
+    fn draw_next_preview(&self, f: &mut Frame<'_>, area: Rect) {
+        if !self.show_preview {
+            return;
+        }
+        let canvas = self.next_flow_slide().and_then(|i| self.slide_canvas(i));
+        let (width, height) = match &canvas {
+            Some(canvas) => miniature_size(
+                canvas.area,
+                (area.width / 4).saturating_sub(2),
+                (area.height / 3).saturating_sub(2),
+            ),
+            None => (12, 1),
+        };
+        let (width, height) = (width + 2, height + 2);
+        let rect = Rect::new(
+            area.right().saturating_sub(width + 1),
+            area.bottom().saturating_sub(height + 1),
+            width,
+            height,
+        )
+        .intersection(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(" Next ");
+        let inner = block.inner(rect);
+        f.render_widget(Clear, rect);
+        f.render_widget(block, rect);
+        match &canvas {
+            Some(canvas) => f.render_widget(Miniature::new(canvas), inner),
+            None => f.render_widget(Paragraph::new("end".dark_gray()), inner),
         }
     }
 
-    fn get_json_slides(&mut self) {
-        let error_string = format!(
-            "file: '{}' failed to open slides json file",
-            self.json_slides
+    fn draw_speaker_notes(&self, f: &mut Frame<'_>, layout: &SlidesLayout) {
+        if !self.show_notes {
+            return;
+        }
+        let notes = self.get_slide().notes.unwrap_or_default();
+        let text = if notes.is_empty() {
+            Text::from("No notes".dark_gray().italic())
+        } else {
+            Text::from(notes)
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(format!(" Notes {} ", self.slide_number(self.slide_index)));
+        let rect = get_notes_layout(layout).intersection(f.size());
+        f.render_widget(Clear, rect);
+        f.render_widget(
+            Paragraph::new(text).wrap(Wrap { trim: false }).block(block),
+            rect,
         );
-        let mut f = std::fs::File::open(self.json_slides.clone()).expect(&error_string);
-        let mut f_content = String::new();
-        f.read_to_string(&mut f_content)
-            .expect("Failed to read json slides file");
-        let slides: SlidesJson = serde_json::from_str(&f_content).unwrap();
+    }
 
-        self.slides = Some(slides);
-        if let Some(slides) = &self.slides {
-            self.slide_count = slides.slides.len();
+    fn draw_nav_hud(&self, f: &mut Frame<'_>, area: Rect) {
+        if !self.nav_hud_visible() {
+            return;
         }
+        let Some(slides) = &self.slides else {
+            return;
+        };
+        let titles: Vec<Option<String>> = slides.slides.iter().map(|s| s.title.clone()).collect();
+        let rect = Rect::new(
+            area.x,
+            area.bottom().saturating_sub(NAV_HUD_HEIGHT),
+            area.width,
+            NAV_HUD_HEIGHT.min(area.height),
+        );
+        let hud = NavHud::new(&titles, self.slide_index).hovered(self.nav_hud_hovered);
+        f.render_widget(hud, rect);
     }
 
-    fn get_slide(&self) -> SlideJson {
-        if let Some(slides) = &self.slides {
-            return slides.slides[self.slide_index].clone();
-        }
-        SlideJson {
-            title: None,
-            content: vec![],
+    /// Edited deck is saved next to the source, text formats are saved as JSON.
+    fn save_slides(&self) {
+        let Some(slides) = &self.source else {
+            return;
+        };
+        if let Err(e) = write_slides(&save_path(&self.json_slides), slides) {
+            log::error!("Failed to save slides: {e:?}");
         }
     }
 
-    fn get_slide_rect(&self, rect: Rect, item_rect: Option<Rect>) -> Rect {
-        let mut slide_rect = Rect::new(rect.x, rect.y, rect.width, rect.height);
-        if let Some(slides) = &self.slides {
-            if let Some(s_content_rect) = item_rect {
-                slide_rect.x += s_content_rect.x;
-                slide_rect.y += s_content_rect.y;
-                slide_rect.width = s_content_rect.width;
-                slide_rect.height = s_content_rect.height;
+    fn edit_slides(&mut self, action: Action) {
+        let edit = match action {
+            Action::MoveSlideLeft => SlideEdit::MoveLeft,
+            Action::MoveSlideRight => SlideEdit::MoveRight,
+            Action::DuplicateSlide => SlideEdit::Duplicate,
+            Action::DeleteSlide => SlideEdit::Delete,
+            _ => return,
+        };
+        let Some(mut source) = self.source.clone() else {
+            return;
+        };
+        let Some(origin) = edit_slide(&mut source.slides, self.get_slide().origin, edit) else {
+            return;
+        };
+        let slides = match present_slides(&source, &self.json_slides) {
+            Ok(slides) => slides,
+            Err(e) => {
+                log::error!("Failed to show edited slides: {e:?}");
+                return;
             }
-        }
-        slide_rect
+        };
+        self.slide_index = slides
+            .slides
+            .iter()
+            .position(|slide| slide.origin == origin)
+            .unwrap_or(0);
+        self.slide_count = slides.slides.len();
+        self.slides = Some(slides);
+        self.source = Some(source);
+        self.history.retain(self.slide_count);
+        self.save_slides();
+        self.needs_enter = true;
     }
 
-    fn store_images(&mut self) {
-        self.images.clear();
-
-        let f_path = Path::new(&self.json_slides);
-        let img_path = f_path.parent().unwrap();
-        let slide = self.get_slide();
+    fn open_prompt(&mut self, kind: PromptKind, value: &str) -> Option<Action> {
+        let title = match kind {
+            PromptKind::Note => format!("Note for slide {}", self.slide_index + 1),
+            PromptKind::Goto => format!("Go to slide (1-{})", self.slide_count),
+            PromptKind::Search => "Search".to_string(),
+        };
+        self.prompt = Some((kind, InputPrompt::new(&title).with_value(value)));
+        Some(Action::Mode(Mode::Input))
+    }
 
-        for item in slide.content {
-            if item.type_ == SlideContentType::Image {
-                let d_img = make_slide_image(item, self.json_slides.clone());
-                if let ReturnSlideWidget::Image(dyn_img) = d_img {
-                    let img_static = self.picker.new_resize_protocol(dyn_img);
-                    self.images.push(img_static);
+    fn submit_prompt(&mut self, kind: PromptKind, value: String) {
+        match kind {
+            PromptKind::Note => {
+                if value.trim().is_empty() {
+                    return;
+                }
+                if let Err(e) = append_note(&self.json_slides, self.slide_index + 1, &value) {
+                    log::error!("Failed to write note: {e:?}");
+                }
+                self.load_questions();
+            }
+            PromptKind::Search => {
+                let slides = self.slides.as_ref().map(|s| s.slides.as_slice());
+                self.search_matches = matching_slides(slides.unwrap_or_default(), &value);
+                let current = self.slide_index;
+                let first = self.search_matches.iter().find(|&&m| m >= current);
+                match first.or(self.search_matches.first()) {
+                    Some(&index) => self.go_to_slide(index),
+                    None => log::info!("No slide matches '{value}'"),
                 }
             }
+            PromptKind::Goto => match value.trim().trim_start_matches(':').parse::<usize>() {
+                Ok(number) if number > 0 => self.go_to_slide(number - 1),
+                _ => log::error!("Invalid slide number '{value}'"),
+            },
         }
     }
 
-    fn next_slide(&mut self) {
-        let mut s_index = self.slide_index + 1;
-        s_index %= self.slide_count;
-        self.slide_index = s_index;
+    /// Action bound to single key, keys of the slide and the deck win over config.
+    fn bound_action(&self, key: KeyEvent) -> Option<Action> {
+        self.slide_keys()
+            .into_iter()
+            .find(|(keys, _)| parse_key_sequence(keys).is_ok_and(|keys| keys == [key]))
+            .map(|(_, action)| action)
+            .or_else(|| self.keybindings.get(&vec![key]).cloned())
+    }
 
-        self.store_images();
+    /// Vim-like motion after typed number, `5 l` or `5 →` goes five slides forward,
+    /// `5 h` or `5 ←` five back and `12 G` to slide shown as 12. Keys go by their bound
+    /// action, counted scroll keys step slides like their vim motions. False when `key`
+    /// is no motion.
+    fn count_motion(&mut self, count: usize, key: KeyEvent) -> bool {
+        let Some(action) = self.bound_action(key) else {
+            return false;
+        };
+        let advances = matches!(
+            action,
+            Action::Next
+                | Action::ScrollRight
+                | Action::Previous
+                | Action::ScrollLeft
+                | Action::Down
+                | Action::ScrollDown
+                | Action::Up
+                | Action::ScrollUp
+        );
+        if advances && self.advance_unconfirmed() {
+            return true;
+        }
+        let slides = self.deck_slides();
+        let current = self.slide_index;
+        let navigation = &self.navigation;
+        let step = match action {
+            Action::Last => numbered_slide(slides, count).map(Step::Slide),
+            Action::Next | Action::ScrollRight => count_steps(slides, current, count, |i| {
+                navigation::next_slide(slides, i, navigation)
+            }),
+            Action::Previous | Action::ScrollLeft => count_steps(slides, current, count, |i| {
+                navigation::previous_slide(slides, i, navigation.wrap).map(Step::Slide)
+            }),
+            Action::Down | Action::ScrollDown => count_steps(slides, current, count, |i| {
+                navigation::down_slide(slides, i, navigation)
+            }),
+            Action::Up | Action::ScrollUp => count_steps(slides, current, count, |i| {
+                navigation::up_slide(slides, i, navigation.wrap).map(Step::Slide)
+            }),
+            _ => return false,
+        };
+        match step {
+            Some(Step::Slide(index)) => self.go_to_slide(index),
+            Some(Step::End) => self.at_end = true,
+            None => {}
+        }
+        true
     }
 
-    fn previous_slide(&mut self) {
-        let mut s_index = self.slide_index;
-        if self.slide_index.checked_sub(1).is_none() {
-            s_index = self.slide_count - 1;
-        } else {
-            s_index -= 1;
+    fn draw_prompt(&self, f: &mut Frame<'_>, content: Rect) {
+        if let Some((_, prompt)) = &self.prompt {
+            let rect = Rect::new(
+                content.x + 2,
+                content.bottom().saturating_sub(4),
+                content.width.saturating_sub(4),
+                3,
+            );
+            f.render_widget(prompt, rect.intersection(f.size()));
         }
-        self.slide_index = s_index;
+    }
 
-        self.store_images();
+    /// Banner over the top of the slide box while the last reload failed.
+    fn draw_reload_error(&self, f: &mut Frame<'_>, content: Rect) {
+        let Some(error) = &self.reload_error else {
+            return;
+        };
+        let rect = Rect::new(content.x + 1, content.y, content.width.saturating_sub(2), 1);
+        let text = format!(" Reload failed, showing last good deck: {} ", error);
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(text.white().on_red()), rect);
+    }
+
+    fn draw_quit_confirm(&self, f: &mut Frame<'_>, area: Rect) {
+        if self
+            .quit_confirm_until
+            .is_none_or(|until| Instant::now() >= until)
+        {
+            return;
+        }
+        let text = " Press quit again to leave kiosk mode ";
+        let width = (text.len() as u16).min(area.width);
+        let rect = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + area.height / 2,
+            width,
+            1,
+        )
+        .intersection(area);
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(text.black().on_yellow()), rect);
+    }
+
+    /// Memory held by decoded images and caches, to tune budgets of big decks.
+    fn draw_profile(&self, f: &mut Frame<'_>, area: Rect) {
+        if !self.show_profile {
+            return;
+        }
+        let (blocks, highlight_bytes) = self.highlights.usage();
+        let frames: usize = self.animations.iter().map(|a| a.frames.len()).sum();
+        let row = |name: &str, value: String| {
+            Line::from(vec![format!("{:<11}", name).dark_gray(), value.into()])
+        };
+        let lines = vec![
+            row(
+                "images",
+                format!("{} ({} frames)", self.images.len(), frames),
+            ),
+            row("decoded", format_bytes(self.image_bytes)),
+            row("highlight", format!("{} blocks", blocks)),
+            row("lines", format_bytes(highlight_bytes)),
+            row("terminals", self.ptys.len().to_string()),
+        ];
+        let width = 30;
+        let height = lines.len() as u16 + 2;
+        let rect = Rect::new(
+            area.right().saturating_sub(width + 1),
+            area.y + 1,
+            width,
+            height,
+        )
+        .intersection(area);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(" profile ");
+        f.render_widget(Clear, rect);
+        f.render_widget(Paragraph::new(lines).block(block), rect);
     }
 
     fn make_title<'a>(slide: &SlideJson) -> BigText<'a> {
@@ -171,63 +2330,443 @@ impl Slides {
         block
     }
 
-    fn make_content_block(&self) -> Block {
-        let s_index = self.slide_index + 1;
-        let title = Line::from(vec![
+    fn make_content_block(&self) -> Block<'_> {
+        let mut title = Line::default();
+        let elapsed = self.talk_elapsed();
+        if let Some(duration) = self.talk_duration {
+            let (remaining, color) = talk_remaining(duration, elapsed.unwrap_or_default());
+            title.spans.push(format!(" {} ", remaining).fg(color));
+        } else if let Some(elapsed) = elapsed {
+            title
+                .spans
+                .push(format!(" {} ", format_duration(elapsed)).dark_gray());
+        }
+        title.spans.extend([
             "|".yellow(),
-            s_index.to_string().green(),
+            self.slide_number(self.slide_index).green(),
             "/".yellow(),
-            self.slide_count.to_string().green(),
+            self.slide_total().to_string().green(),
             "|".yellow(),
         ]);
-        Self::make_block(None)
+        let mut block = Self::make_block(None)
             .title_bottom(title)
             .title_alignment(Alignment::Right)
-            .border_type(BorderType::Rounded)
+            .border_type(BorderType::Rounded);
+        if let Some(border) = theme().border {
+            block = block.border_set(border);
+        }
+        if self.advance_pending() {
+            block = block.title(
+                Title::from(" PRESS AGAIN TO LEAVE ".black().on_yellow())
+                    .alignment(Alignment::Center)
+                    .position(Position::Top),
+            );
+        }
+        if self.autoplay.is_some() && self.autoplay_paused {
+            block = block.title(
+                Title::from(" PAUSED ".black().on_cyan())
+                    .alignment(Alignment::Center)
+                    .position(Position::Top),
+            );
+        }
+        if self.offline {
+            block = block.title(
+                Title::from(" OFFLINE ".black().on_yellow())
+                    .alignment(Alignment::Center)
+                    .position(Position::Bottom),
+            );
+        }
+        if self.edit_mode {
+            block = block.title(
+                Title::from(" EDIT ".black().on_red())
+                    .alignment(Alignment::Left)
+                    .position(Position::Bottom),
+            );
+        }
+        block
     }
 
-    fn make_slide_items<'a>(
-        slide: &SlideJson,
-        json_slides: String,
-    ) -> Vec<(
-        ReturnSlideWidget<'a>,
-        String,
-        Option<Rect>,
-        Option<Vec<u64>>,
-    )> {
+    fn make_slide_items<'a>(&self, slide: &SlideJson) -> Vec<SlideItem<'a>> {
+        let json_slides = self.json_slides.clone();
         let mut slide_items = vec![];
-        for item in &slide.content {
-            slide_items.push((
-                make_slide_content(item.clone(), json_slides.clone()),
-                get_slide_content_string(&item),
-                item.rect,
-                item.data.clone(),
-            ));
+        for item in self.flatten_items(slide) {
+            let is_image = item.type_ == SlideContentType::Image
+                || diagram_image_item(&item, &json_slides).is_some();
+            let widget = (!is_image).then(|| make_slide_content(item.clone(), json_slides.clone()));
+            slide_items.push((widget, item));
         }
         slide_items
     }
+
+    /// Highlighted lines of code item, when the frame is over budget the code is
+    /// highlighted in background and shown plain until it is done.
+    fn code_lines(&self, item: &ContentJson, deadline: Instant) -> Vec<Line<'static>> {
+        let block = self.code_block(item);
+        if let Some(lines) = self.highlights.get(&block) {
+            return lines.to_vec();
+        }
+        if Instant::now() > deadline {
+            let plain = block
+                .code
+                .split('\n')
+                .map(|l| Line::from(l.to_string()))
+                .collect();
+            self.highlights.highlight_in_background(vec![block]);
+            return plain;
+        }
+        self.highlights.get_or_highlight(&block).to_vec()
+    }
+
+    /// Image is drawn once decoded, until then only its frame is shown.
+    fn draw_image(&self, f: &mut Frame<'_>, slide_rect: Rect, img_index: usize) {
+        // -- block | borders
+        let block = Self::make_block(None)
+            .style(Style::default().bg(Color::Black))
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)));
+        let mut b_rect = slide_rect;
+        b_rect.x -= 1;
+        b_rect.width += 2;
+        b_rect.y -= 1;
+        f.render_widget(block, b_rect);
+
+        // -- image
+        match self.images.get(img_index) {
+            Some(ImageSlot::Ready(img)) => {
+                let mut img_static = img.clone();
+                let img = StatefulImage::new(None).resize(Resize::Fit(None));
+                f.render_stateful_widget(img, slide_rect, &mut img_static);
+            }
+            Some(ImageSlot::Failed(e)) => {
+                f.render_widget(Paragraph::new(e.as_str()).red(), slide_rect);
+            }
+            None => {}
+        }
+    }
 }
 
 impl Component for Slides {
+    fn register_action_handler(&mut self, tx: UnboundedSender<Action>) -> Result<()> {
+        self.action_tx = Some(tx);
+        Ok(())
+    }
+
+    fn register_config_handler(&mut self, config: Config) -> Result<()> {
+        self.audio = AudioPlayer::new(config.audio);
+        self.terminal = config.terminal;
+        self.render = config.render;
+        self.exec = config.exec;
+        self.code = config.code;
+        self.navigation = config.navigation;
+        self.progress = config.progress;
+        self.keybindings = config
+            .keybindings
+            .get(&Mode::Home)
+            .cloned()
+            .unwrap_or_default();
+        Ok(())
+    }
+
     fn init(&mut self, area: Rect, json_slides: String) -> Result<()> {
         self.json_slides = json_slides;
-        self.picker.guess_protocol();
-        self.get_json_slides();
+        self.init_picker();
+        self.get_json_slides()?;
+        self.start_presenter();
+        self.start_slide();
         self.store_images();
+        self.start_terminals();
         Ok(())
     }
 
+    fn handle_mouse_events(&mut self, mouse: MouseEvent) -> Result<Option<Action>> {
+        if self.drawing {
+            self.handle_draw_mouse(mouse);
+            return Ok(None);
+        }
+        self.handle_pointer_mouse(mouse);
+        let action = self.handle_click_mouse(mouse);
+        self.handle_nav_hud_mouse(mouse);
+        Ok(action)
+    }
+
+    fn handle_key_events(&mut self, key: KeyEvent) -> Result<Option<Action>> {
+        if self.pty_focus {
+            return Ok(self.handle_terminal_key(key));
+        }
+        if self.overview.is_some() {
+            return Ok(self.handle_overview_key(key));
+        }
+        if self.palette.is_some() {
+            return Ok(self.handle_palette_key(key));
+        }
+        if let Some(Zoom::Selecting(selected)) = self.zoom {
+            return Ok(self.handle_zoom_key(selected, key));
+        }
+        if let Some(bookmark_key) = self.bookmark_key.take() {
+            if let KeyCode::Char(letter) = key.code {
+                self.handle_bookmark(bookmark_key, letter);
+            }
+            return Ok(Some(Action::Mode(Mode::Home)));
+        }
+        // -- number typed in goto prompt is count of motion key
+        let count = match &self.prompt {
+            Some((PromptKind::Goto, prompt)) => prompt.value().parse::<usize>().ok(),
+            _ => None,
+        };
+        if count.is_some_and(|count| self.count_motion(count, key)) {
+            self.prompt = None;
+            return Ok(Some(Action::Mode(Mode::Home)));
+        }
+        let Some((kind, prompt)) = &mut self.prompt else {
+            return Ok(None);
+        };
+        let kind = *kind;
+        match prompt.handle_key(key) {
+            PromptEvent::Submit(value) => {
+                self.prompt = None;
+                self.submit_prompt(kind, value);
+                Ok(Some(Action::Mode(Mode::Home)))
+            }
+            PromptEvent::Cancel => {
+                self.prompt = None;
+                Ok(Some(Action::Mode(Mode::Home)))
+            }
+            PromptEvent::Changed => Ok(None),
+        }
+    }
+
     fn update(&mut self, action: Action) -> Result<Option<Action>> {
         match action {
+            Action::Quit => {
+                if let (Some(rehearsal), Some(slides)) = (&mut self.rehearsal, &self.slides) {
+                    match rehearsal.write(&self.json_slides, &slides.slides) {
+                        Ok(path) => log::info!("rehearsal report written to {}", path.display()),
+                        Err(e) => log::error!("{e}"),
+                    }
+                }
+            }
+            Action::CaptureNote => {
+                return Ok(self.open_prompt(PromptKind::Note, ""));
+            }
+            Action::SearchPrompt => {
+                return Ok(self.open_prompt(PromptKind::Search, ""));
+            }
+            Action::SearchNext | Action::SearchPrevious => {
+                let backwards = action == Action::SearchPrevious;
+                if let Some(index) = next_match(&self.search_matches, self.slide_index, backwards) {
+                    self.go_to_slide(index);
+                }
+            }
+            Action::ClearSearch => {
+                self.search_matches.clear();
+            }
+            Action::Cancel => {
+                self.cancel();
+            }
+            Action::ToggleHelp => {
+                self.show_help = !self.show_help;
+            }
+            Action::ToggleBlank => {
+                self.blank = !self.blank;
+            }
+            Action::ConfirmQuit => {
+                self.quit_confirm_until = Some(Instant::now() + QUIT_CONFIRM_TIMEOUT);
+            }
+            Action::ToggleFreeze => {
+                self.frozen = match self.frozen {
+                    Some(_) => None,
+                    None => Some(None),
+                };
+                log::info!(
+                    "Frame {}",
+                    if self.frozen.is_some() {
+                        "frozen"
+                    } else {
+                        "live"
+                    }
+                );
+            }
+            Action::ToggleLoop => {
+                self.navigation.wrap = !self.navigation.wrap;
+                log::info!("Loop {}", if self.navigation.wrap { "on" } else { "off" });
+            }
+            Action::ToggleAutoplay if self.autoplay.is_some() => {
+                self.autoplay_paused = !self.autoplay_paused;
+                self.autoplay_since = Instant::now();
+            }
+            Action::ToggleToc => {
+                self.show_toc = !self.show_toc;
+            }
+            Action::ResetTimer => {
+                self.reset_talk_clock();
+            }
+            Action::MovePointer(dx, dy) => {
+                let area = self.content_area;
+                let position = self
+                    .pointer
+                    .unwrap_or((area.x + area.width / 2, area.y + area.height / 2));
+                self.pointer = Some(moved(position, dx, dy, self.area));
+            }
+            Action::Zoom => {
+                if self.zoom.is_some() {
+                    self.zoom = None;
+                } else if !self.flatten_items(&self.get_slide()).is_empty() {
+                    self.zoom = Some(Zoom::Selecting(0));
+                    return Ok(Some(Action::Mode(Mode::Input)));
+                }
+            }
+            Action::ToggleDraw => {
+                self.drawing = !self.drawing;
+            }
+            Action::NextDrawColor => {
+                self.draw_color = (self.draw_color + 1) % DRAW_COLORS.len();
+                self.drawing = true;
+            }
+            Action::UndoStroke => {
+                if let Some(annotation) = self.annotations.get_mut(&self.slide_index) {
+                    annotation.undo();
+                }
+            }
+            Action::ClearDrawing => {
+                self.annotations.remove(&self.slide_index);
+            }
+            Action::TogglePreview => {
+                self.show_preview = !self.show_preview;
+            }
+            Action::ToggleNotes => {
+                self.show_notes = !self.show_notes;
+            }
+            Action::ToggleOverview => {
+                return Ok(self.toggle_overview());
+            }
+            Action::OpenPalette => {
+                self.palette = Some((String::new(), 0));
+                return Ok(Some(Action::Mode(Mode::Input)));
+            }
+            Action::GotoPrompt(value) => {
+                return Ok(self.open_prompt(PromptKind::Goto, &value));
+            }
+            Action::ToggleAnswered => {
+                self.toggle_answered();
+            }
+            Action::RunCode => {
+                self.run_code();
+            }
+            Action::CopyCode => {
+                self.copy_code();
+            }
+            Action::ToggleProfile => {
+                self.show_profile = !self.show_profile;
+            }
+            Action::FocusTerminal => {
+                return Ok(self.focus_terminal());
+            }
+            Action::GotoSlide(number) => {
+                self.go_to_slide(number.saturating_sub(1));
+            }
+            Action::GotoId(id) => {
+                self.go_to_id(&id);
+            }
+            Action::FollowLink => {
+                let option = self
+                    .menu_options()
+                    .and_then(|options| options.get(self.menu_index).cloned());
+                if let Some(id) = option.map(|o| o.goto).or_else(|| self.link_at(None)) {
+                    self.go_to_id(&id);
+                }
+            }
+            Action::RunHook(command) => {
+                exec::run_hook(&command, deck_dir(&self.json_slides));
+            }
+            Action::Tick | Action::Render => {
+                self.receive_images();
+                self.advance_animations();
+                self.advance_autoplay();
+                self.follow_presenter();
+                self.refresh_code();
+            }
+            Action::Next | Action::Down if self.has_pending_items() => {
+                self.reveal_all = true;
+            }
+            Action::Next | Action::Down if self.has_pending_steps() => {
+                self.code_step += 1;
+            }
+            Action::Up | Action::Down if self.menu_options().is_some() => {
+                self.select_menu_option(action == Action::Down);
+            }
+            Action::Next | Action::Previous | Action::Up | Action::Down
+                if self.advance_unconfirmed() => {}
             Action::Next => {
                 self.next_slide();
             }
+            Action::Down => {
+                self.down_slide();
+            }
             Action::Previous => {
                 self.previous_slide();
             }
+            Action::Up => {
+                self.up_slide();
+            }
+            Action::Back => {
+                if let Some(index) = self.history.back(self.slide_index) {
+                    self.show_slide(index);
+                }
+            }
+            Action::Forward => {
+                if let Some(index) = self.history.forward(self.slide_index) {
+                    self.show_slide(index);
+                }
+            }
+            Action::SetBookmark => {
+                self.bookmark_key = Some(BookmarkKey::Set);
+                return Ok(Some(Action::Mode(Mode::Input)));
+            }
+            Action::JumpBookmark => {
+                self.bookmark_key = Some(BookmarkKey::Jump);
+                return Ok(Some(Action::Mode(Mode::Input)));
+            }
+            Action::First => {
+                self.go_to_slide(0);
+            }
+            Action::Last => {
+                self.go_to_slide(self.slide_count.saturating_sub(1));
+            }
+            Action::NextTab => {
+                self.next_tab();
+            }
+            Action::ScrollDown => {
+                self.scroll_down();
+            }
+            Action::ScrollUp => {
+                self.scroll_up();
+            }
+            Action::ScrollLeft => {
+                self.scroll_left();
+            }
+            Action::ScrollRight => {
+                self.scroll_right();
+            }
+            Action::ToggleEdit => {
+                self.edit_mode = !self.edit_mode;
+            }
+            Action::MoveSlideLeft
+            | Action::MoveSlideRight
+            | Action::DuplicateSlide
+            | Action::DeleteSlide
+                if self.edit_mode =>
+            {
+                self.edit_slides(action);
+            }
             Action::Reload => {
-                self.get_json_slides();
+                self.reload_error = match self.get_json_slides() {
+                    Ok(()) => None,
+                    Err(e) => {
+                        log::error!("Failed to reload slides: {}", e.report());
+                        Some(e.report())
+                    }
+                };
                 self.store_images();
+                self.start_terminals();
             }
             _ => {}
         }
@@ -235,6 +2774,26 @@ impl Component for Slides {
     }
 
     fn draw(&mut self, f: &mut Frame<'_>, area: Rect) -> Result<()> {
+        self.enter_pending_slide();
+        if self.presenter_view {
+            self.draw_presenter(f, area);
+            self.area = area;
+            return Ok(());
+        }
+        if let Some(Some(frozen)) = &self.frozen {
+            // -- resized terminal can not show the old frame
+            if frozen.area == *f.buffer_mut().area() {
+                *f.buffer_mut() = frozen.clone();
+                return Ok(());
+            }
+            self.frozen = None;
+        }
+        if self.blank {
+            f.render_widget(Clear, area);
+            f.render_widget(Block::default().bg(Color::Black), area);
+            self.area = area;
+            return Ok(());
+        }
         let mut box_width = CONTENT_WIDTH;
         let mut box_height = CONTENT_HEIGHT;
         if let Some(slides) = &self.slides {
@@ -250,28 +2809,79 @@ impl Component for Slides {
             rect.content.height,
         );
 
-        let slide = self.get_slide();
+        let slide = if self.at_end {
+            SlideJson::new(Some(END_SCREEN_TITLE.to_string()), vec![])
+        } else {
+            self.get_slide()
+        };
 
-        let slide_items = Self::make_slide_items(&slide, self.json_slides.clone());
+        let deadline = Instant::now() + Duration::from_millis(self.render.timeout_ms);
+        let slide_items = self.make_slide_items(&slide);
+        let zoomed = match self.zoom {
+            Some(Zoom::Zoomed(zoomed)) => Some(zoomed),
+            _ => None,
+        };
         let title = Self::make_title(&slide);
         let block = self.make_content_block();
 
-        f.render_widget(title, title_rect);
+        if zoomed.is_none() {
+            f.render_widget(title, title_rect);
+        }
         f.render_widget(block, rect.content);
+        if self.progress.enabled {
+            let border = theme()
+                .border
+                .map_or("─", |border| border.horizontal_bottom);
+            let ratio = if self.at_end {
+                1.0
+            } else {
+                self.deck_progress()
+            };
+            f.render_widget(ProgressBar::new(ratio, border), rect.content);
+        }
+        if self.at_end {
+            let hint = Paragraph::new("end of slides, press previous to go back".dark_gray())
+                .alignment(Alignment::Center);
+            let hint_rect = Rect::new(
+                rect.content.x,
+                rect.content.y + rect.content.height / 2,
+                rect.content.width,
+                1,
+            );
+            f.render_widget(hint, hint_rect);
+        }
 
         // -- render slide widgets
         let mut img_index = 0;
-        for (slide, c, r, d) in slide_items {
-            let slide_rect = self.get_slide_rect(rect.content, r);
-
-            let mut data = vec![];
-            if let Some(d1) = d {
-                data = d1;
+        let mut pty_index = 0;
+        let pty_keys: Vec<u64> = self.pty_items().into_iter().map(|(key, _)| key).collect();
+        let focused_pty = self.focused_pty();
+        for (index, (slide, item)) in slide_items.into_iter().enumerate() {
+            if !self.is_revealed(&item) || zoomed.is_some_and(|zoomed| zoomed != index) {
+                match slide {
+                    None => img_index += 1,
+                    Some(ReturnSlideWidget::Terminal) => pty_index += 1,
+                    _ => {}
+                }
+                continue;
             }
+            // -- items of the fixed size box may run past the terminal edge
+            let slide_rect = self
+                .get_slide_rect(rect.content, item.rect)
+                .intersection(area);
+            let Some(slide) = slide else {
+                self.draw_image(f, slide_rect, img_index);
+                img_index += 1;
+                continue;
+            };
+            let c = get_slide_content_string(&item);
+            let data = item.data.clone().unwrap_or_default();
 
             match slide {
                 ReturnSlideWidget::Paragraph(s) => {
-                    f.render_widget(s, slide_rect);
+                    let max = self.max_item_scroll(index, &item);
+                    let offset = self.scroll.min(max);
+                    self.render_scrollable(f, s.scroll((offset, 0)), max, slide_rect);
                 }
                 ReturnSlideWidget::Line(s) => {
                     f.render_widget(s, slide_rect);
@@ -279,23 +2889,8 @@ impl Component for Slides {
                 ReturnSlideWidget::BigText(s) => {
                     f.render_widget(s, slide_rect);
                 }
-                ReturnSlideWidget::Image(s) => {
-                    // -- block | borders
-                    let block = Self::make_block(None)
-                        .style(Style::default().bg(Color::Black))
-                        .border_style(Style::default().fg(Color::Rgb(100, 100, 100)));
-                    let mut b_rect = slide_rect;
-                    b_rect.x -= 1;
-                    b_rect.width += 2;
-                    b_rect.y -= 1;
-                    f.render_widget(block, b_rect);
-
-                    // -- image
-                    let mut img_static = self.images[img_index].clone();
-                    let img = StatefulImage::new(None).resize(Resize::Fit(None));
-                    f.render_stateful_widget(img, slide_rect, &mut img_static);
-                    img_index += 1;
-                }
+                // -- images are decoded ahead and drawn above
+                ReturnSlideWidget::Image(_) => {}
                 ReturnSlideWidget::Block(s) => {
                     f.render_widget(s, slide_rect);
                 }
@@ -303,33 +2898,230 @@ impl Component for Slides {
                     s = s.data(&data);
                     f.render_widget(s, slide_rect);
                 }
-                ReturnSlideWidget::CodeHighlight(mut l) => {
-                    let ps = SyntaxSet::load_defaults_newlines();
-                    let ts = ThemeSet::load_defaults();
-                    let syntax = ps.find_syntax_by_extension("rs").unwrap();
-                    let mut h = HighlightLines::new(syntax, &ts.themes["base16-ocean.dark"]);
-
-                    let mut lines: Vec<Line> = vec![];
-                    let c_lines: Vec<&str> = c.split('\n').collect();
-
-                    for c_line in c_lines {
-                        for line in LinesWithEndings::from(c_line) {
-                            let l_spans: Vec<Span> = h
-                                .highlight_line(line, &ps)
-                                .unwrap()
-                                .into_iter()
-                                .filter_map(|seg| into_span(seg).ok())
-                                .collect();
-                            let line = Line::from(l_spans);
-                            lines.push(line);
-                        }
+                ReturnSlideWidget::CodeHighlight(_) => {
+                    let mut lines = self.code_lines(&item, deadline);
+                    if let (Some(typewriter), Some(units)) =
+                        (&item.typewriter, self.typed_units(&item))
+                    {
+                        lines = type_lines(lines, typewriter, units);
+                    }
+                    if let Some(shown) = self.step_lines(&item) {
+                        lines.truncate(shown);
+                    }
+                    let start_line = item.start_line.unwrap_or(1);
+                    if let Some(highlighted) = &item.highlight_lines {
+                        lines = emphasize_lines(lines, start_line, highlighted);
+                    }
+                    let callouts = item.callouts.clone().unwrap_or_default();
+                    let legend = callout_legend(&callouts, start_line, lines.len());
+                    lines = mark_callouts(lines, start_line, &callouts);
+                    if item.line_numbers == Some(true) {
+                        lines = number_lines(lines, start_line);
+                    }
+                    let mut area = slide_rect;
+                    if let Some(chrome) = self.code_chrome(&item) {
+                        area = chrome.inner(slide_rect);
+                        f.render_widget(chrome, slide_rect);
+                    }
+                    let legend_height = Self::legend_height(&item, area.height);
+                    area.height -= legend_height;
+                    let legend_rect = Rect::new(area.x, area.bottom(), area.width, legend_height);
+                    f.render_widget(Paragraph::new(legend), legend_rect);
+                    let mut code_rect = area;
+                    if let Some(run) = self.exec_runs.get(&index) {
+                        let output_height = exec_output_height(area.height);
+                        code_rect.height -= output_height;
+                        let output_rect =
+                            Rect::new(area.x, code_rect.bottom(), area.width, output_height);
+                        Self::draw_exec_output(f, run, output_rect);
+                    }
+                    let max = self.max_item_scroll(index, &item);
+                    let offset = self.scroll.min(max);
+                    let h_offset = self.h_scroll.min(self.max_item_h_scroll(index, &item));
+                    let mut code = Paragraph::new(lines.clone());
+                    if item.wrap == Some(true) {
+                        code = code.wrap(Wrap { trim: false }).scroll((offset, 0));
+                    } else {
+                        code = code.scroll((offset, h_offset));
+                    }
+                    let text_rect = self.render_scrollable(f, code, max, code_rect);
+                    if item.wrap != Some(true) {
+                        Self::draw_clip_markers(f, &lines, (offset, h_offset), text_rect);
                     }
-                    l = Paragraph::new(lines);
-
-                    f.render_widget(l, slide_rect);
+                }
+                ReturnSlideWidget::Pie(p) => {
+                    f.render_widget(p, slide_rect);
+                }
+                ReturnSlideWidget::Table(t) => {
+                    f.render_widget(t, slide_rect);
+                }
+                ReturnSlideWidget::Menu => {
+                    let options = item.options.clone().unwrap_or_default();
+                    let selected = self.menu_index.min(options.len().saturating_sub(1));
+                    f.render_widget(MenuWidget::new(&options, selected), slide_rect);
+                }
+                ReturnSlideWidget::Questions => {
+                    let selected = self
+                        .question_index
+                        .min(self.questions.len().saturating_sub(1));
+                    f.render_widget(QuestionList::new(&self.questions, selected), slide_rect);
+                }
+                ReturnSlideWidget::QrCode(q) => {
+                    f.render_widget(q, slide_rect);
+                }
+                ReturnSlideWidget::Icon(i) => {
+                    f.render_widget(i, slide_rect);
+                }
+                ReturnSlideWidget::Plot(p) => {
+                    f.render_widget(p, slide_rect);
+                }
+                ReturnSlideWidget::Rule(r) => {
+                    f.render_widget(r, slide_rect);
+                }
+                ReturnSlideWidget::Clock(c) => {
+                    f.render_widget(c, slide_rect);
+                }
+                ReturnSlideWidget::Terminal => {
+                    let key = pty_keys.get(pty_index).copied();
+                    let focused = self.pty_focus && key.is_some() && key == focused_pty;
+                    let command = self.pty_command(&item);
+                    if let Some(pty) = key.and_then(|key| self.ptys.get_mut(&key)) {
+                        let rows = slide_rect.height.saturating_sub(2).max(1);
+                        let cols = slide_rect.width.saturating_sub(2).max(1);
+                        pty.session.resize(rows, cols);
+                        let parser = pty.session.parser();
+                        if let Ok(parser) = parser.lock() {
+                            let terminal =
+                                TerminalWidget::new(parser.screen(), &command).focused(focused);
+                            f.render_widget(terminal, slide_rect);
+                        };
+                    }
+                    pty_index += 1;
+                }
+                ReturnSlideWidget::Countdown(c) => {
+                    f.render_widget(c.elapsed(self.slide_start.elapsed()), slide_rect);
+                }
+                ReturnSlideWidget::Tabs(t) => {
+                    let tab_count = item.tabs.as_ref().map(|t| t.len()).unwrap_or(0);
+                    let bar_rect = Rect::new(slide_rect.x, slide_rect.y, slide_rect.width, 1);
+                    let line_rect = Rect::new(slide_rect.x, slide_rect.y + 1, slide_rect.width, 1);
+                    f.render_widget(t.select(self.tab_index % tab_count.max(1)), bar_rect);
+                    f.render_widget(
+                        Block::default()
+                            .borders(Borders::TOP)
+                            .border_style(Style::default().fg(Color::Rgb(100, 100, 100))),
+                        line_rect,
+                    );
                 }
             }
         }
+        if let Some(annotation) = self.annotations.get(&self.slide_index) {
+            f.render_widget(AnnotationLayer::new(annotation), area);
+        }
+        self.draw_drawing_hint(f, rect.content);
+        self.draw_zoom(f, rect.content);
+        self.draw_toc(f, &rect);
+        self.draw_speaker_notes(f, &rect);
+        self.draw_next_preview(f, area);
+        self.draw_prompt(f, rect.content);
+        self.draw_profile(f, area);
+        self.draw_nav_hud(f, area);
+        self.draw_overview(f, area);
+        self.draw_palette(f, area);
+        self.draw_help(f, area);
+        self.draw_quit_confirm(f, area);
+        self.draw_reload_error(f, rect.content);
+        if let Some(position) = self.pointer {
+            f.render_widget(Pointer::new(position), area);
+        }
+        self.area = area;
+        self.content_area = rect.content;
+        if let Some(frozen @ None) = &mut self.frozen {
+            *frozen = Some(f.buffer_mut().clone());
+        }
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn slides_of(json: &str) -> Slides {
+        let deck: SlidesJson = serde_json::from_str(json).unwrap();
+        let mut slides = Slides::new();
+        slides.slide_count = deck.slides.len();
+        slides.slides = Some(deck);
+        slides
+    }
+
+    #[test]
+    fn test_clip_markers_outside_buffer() {
+        let mut terminal = Terminal::new(backend::TestBackend::new(20, 5)).unwrap();
+        let lines = vec![Line::from("let wide = \"code line wider than its block\";")];
+        terminal
+            .draw(|f| {
+                Slides::draw_clip_markers(f, &lines, (0, 2), Rect::new(30, 1, 10, 3));
+                Slides::draw_clip_markers(f, &lines, (0, 2), Rect::new(15, 1, 10, 3));
+            })
+            .unwrap();
+        let buf = terminal.backend().buffer();
+        let caps = capabilities();
+        assert_eq!(
+            buf.get(15, 1).symbol(),
+            caps.glyph(UnicodeLevel::Basic, "‹", "<")
+        );
+        assert_eq!(
+            buf.get(19, 1).symbol(),
+            caps.glyph(UnicodeLevel::Basic, "›", ">")
+        );
+    }
+
+    #[test]
+    fn test_cancel_closes_topmost_overlay() {
+        let mut slides = slides_of(
+            r#"{
+  "box_size": { "width": 90, "height": 35 },
+  "slides": [{ "title": "ONE", "content": [] }]
+}"#,
+        );
+        slides.search_matches = vec![0];
+        slides.reload_error = Some("broken".to_string());
+        slides.pointer = Some((1, 1));
+        slides.drawing = true;
+        slides.zoom = Some(Zoom::Zoomed(0));
+        slides.show_help = true;
+        slides.update(Action::Cancel).unwrap();
+        assert!(!slides.show_help);
+        assert_eq!(slides.zoom, Some(Zoom::Zoomed(0)));
+        slides.update(Action::Cancel).unwrap();
+        assert_eq!(slides.zoom, None);
+        assert!(slides.drawing);
+        for _ in 0..3 {
+            slides.update(Action::Cancel).unwrap();
+        }
+        assert!(!slides.drawing && slides.pointer.is_none() && slides.reload_error.is_none());
+        assert_eq!(slides.search_matches, vec![0]);
+        slides.update(Action::Cancel).unwrap();
+        assert!(slides.search_matches.is_empty());
+    }
+
+    #[test]
+    fn test_down_asks_before_leaving_confirm_advance_slide() {
+        let mut slides = slides_of(
+            r#"{
+  "box_size": { "width": 90, "height": 35 },
+  "slides": [
+    { "title": "DEMO", "content": [], "confirm_advance": true },
+    { "title": "AFTER", "content": [] }
+  ]
+}"#,
+        );
+        slides.update(Action::Down).unwrap();
+        assert_eq!(slides.slide_index, 0);
+        slides.update(Action::Down).unwrap();
+        assert_eq!(slides.slide_index, 1);
+    }
+}
@@ -13,7 +13,7 @@ impl Title {
         Self {}
     }
 
-    fn make_title(&self) -> Paragraph {
+    fn make_title(&self) -> Paragraph<'_> {
         let version: &str = env!("CARGO_PKG_VERSION");
         let title = format!("tui-slides (v{})", version);
 
@@ -31,6 +31,224 @@ pub struct Config {
   pub keybindings: KeyBindings,
   #[serde(default)]
   pub styles: Styles,
+  #[serde(default)]
+  pub audio: AudioConfig,
+  #[serde(default)]
+  pub terminal: TerminalConfig,
+  #[serde(default)]
+  pub notifications: NotificationsConfig,
+  #[serde(default)]
+  pub heartbeat: HeartbeatConfig,
+  #[serde(default)]
+  pub cache: CacheConfig,
+  #[serde(default)]
+  pub render: RenderConfig,
+  #[serde(default)]
+  pub mouse: MouseConfig,
+  #[serde(default)]
+  pub exec: ExecConfig,
+  #[serde(default)]
+  pub code: CodeConfig,
+  #[serde(default)]
+  pub navigation: NavigationConfig,
+  #[serde(default)]
+  pub progress: ProgressConfig,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct AudioConfig {
+  #[serde(default)]
+  pub mute: bool,
+  /// Playback volume in percent.
+  #[serde(default = "default_volume")]
+  pub volume: u8,
+}
+
+fn default_volume() -> u8 {
+  100
+}
+
+impl Default for AudioConfig {
+  fn default() -> Self {
+    Self { mute: false, volume: default_volume() }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct TerminalConfig {
+  /// Show current slide in terminal window title.
+  #[serde(default = "default_true")]
+  pub title: bool,
+  /// Send terminal notification on slide change.
+  #[serde(default)]
+  pub notify: bool,
+}
+
+fn default_true() -> bool {
+  true
+}
+
+impl Default for TerminalConfig {
+  fn default() -> Self {
+    Self { title: default_true(), notify: false }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct Milestone {
+  /// Minutes since start of the presentation.
+  pub minutes: u64,
+  pub message: String,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct NotificationsConfig {
+  #[serde(default)]
+  pub milestones: Vec<Milestone>,
+  /// Send notifications with `notify-send`.
+  #[serde(default = "default_true")]
+  pub notify_send: bool,
+  /// Send notifications through the terminal (OSC 777).
+  #[serde(default = "default_true")]
+  pub osc: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct HeartbeatConfig {
+  /// Seconds between heartbeats, `0` disables them.
+  #[serde(default)]
+  pub interval_secs: u64,
+  /// File heartbeats are appended to as JSON lines.
+  #[serde(default)]
+  pub file: Option<PathBuf>,
+  /// `host:port` heartbeats are sent to as UDP datagrams.
+  #[serde(default)]
+  pub udp: Option<String>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CacheConfig {
+  /// Size of deck cache in megabytes above which unused files are removed.
+  #[serde(default = "default_cache_size")]
+  pub max_size_mb: u64,
+}
+
+fn default_cache_size() -> u64 {
+  500
+}
+
+impl Default for CacheConfig {
+  fn default() -> Self {
+    Self { max_size_mb: default_cache_size() }
+  }
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct RenderConfig {
+  /// Time in milliseconds highlighting code of a slide may take in one frame, code
+  /// over it is shown as plain text and highlighted in background.
+  #[serde(default = "default_render_timeout")]
+  pub timeout_ms: u64,
+}
+
+fn default_render_timeout() -> u64 {
+  50
+}
+
+impl Default for RenderConfig {
+  fn default() -> Self {
+    Self { timeout_ms: default_render_timeout() }
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct MouseConfig {
+  /// Capture mouse, used only when the terminal supports it.
+  #[serde(default)]
+  pub enabled: bool,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct NavigationConfig {
+  /// Next on the last slide goes to the first one and Previous on the first to the last.
+  #[serde(default = "default_loop", rename = "loop")]
+  pub wrap: bool,
+  /// Next on the last slide shows end of deck screen, when not wrapping.
+  #[serde(default)]
+  pub end_screen: bool,
+}
+
+fn default_loop() -> bool {
+  true
+}
+
+impl Default for NavigationConfig {
+  fn default() -> Self {
+    Self { wrap: default_loop(), end_screen: false }
+  }
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct ProgressConfig {
+  /// Progress through the deck drawn along the bottom border of the slide.
+  #[serde(default)]
+  pub enabled: bool,
+  /// Slides weigh by their `duration_secs` instead of counting the same.
+  #[serde(default)]
+  pub weighted: bool,
+}
+
+#[derive(Clone, Debug, Default, Deserialize)]
+pub struct CodeConfig {
+  /// Folder of `.sublime-syntax` files added to syntaxes of every deck, relative
+  /// paths are relative to the config directory.
+  #[serde(default)]
+  pub syntaxes: Option<PathBuf>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct ExecConfig {
+  /// Command running code of given language, `{file}` is replaced with path of the
+  /// code, otherwise the path is appended.
+  #[serde(default = "default_interpreters")]
+  pub interpreters: HashMap<String, String>,
+  /// REPL of `Repl` items by language.
+  #[serde(default = "default_repls")]
+  pub repls: HashMap<String, String>,
+}
+
+fn default_repls() -> HashMap<String, String> {
+  [("py", "python3"), ("python", "python3"), ("js", "node"), ("rs", "evcxr"), ("rb", "irb")]
+    .into_iter()
+    .map(|(language, repl)| (language.to_string(), repl.to_string()))
+    .collect()
+}
+
+fn default_interpreters() -> HashMap<String, String> {
+  [
+    ("sh", "sh"),
+    ("bash", "bash"),
+    ("py", "python3"),
+    ("python", "python3"),
+    ("js", "node"),
+    ("rb", "ruby"),
+    ("rs", "rustc --edition 2021 {file} -o {file}.bin && {file}.bin"),
+  ]
+  .into_iter()
+    .map(|(language, interpreter)| (language.to_string(), interpreter.to_string()))
+    .collect()
+}
+
+impl Default for ExecConfig {
+  fn default() -> Self {
+    Self { interpreters: default_interpreters(), repls: default_repls() }
+  }
+}
+
+impl Default for NotificationsConfig {
+  fn default() -> Self {
+    Self { milestones: vec![], notify_send: default_true(), osc: default_true() }
+  }
 }
 
 impl Config {
@@ -71,7 +289,7 @@ impl Config {
     for (mode, default_styles) in default_config.styles.iter() {
       let user_styles = cfg.styles.entry(*mode).or_default();
       for (style_key, style) in default_styles.iter() {
-        user_styles.entry(style_key.clone()).or_insert_with(|| style.clone());
+        user_styles.entry(style_key.clone()).or_insert_with(|| *style);
       }
     }
 
@@ -201,7 +419,7 @@ pub fn key_event_to_string(key_event: &KeyEvent) -> String {
       char = format!("f({c})");
       &char
     },
-    KeyCode::Char(c) if c == ' ' => "space",
+    KeyCode::Char(' ') => "space",
     KeyCode::Char(c) => {
       char = c.to_string();
       &char
@@ -392,6 +610,22 @@ mod tests {
 
   use super::*;
 
+  #[test]
+  fn test_audio_config_defaults() {
+    let config: Config = json5::from_str(r#"{ "audio": { "mute": true } }"#).unwrap();
+    assert!(config.audio.mute);
+    assert_eq!(config.audio.volume, 100);
+  }
+
+  #[test]
+  fn test_notifications_config() {
+    let config: Config =
+      json5::from_str(r#"{ "notifications": { "milestones": [{ "minutes": 25, "message": "5 minutes left" }] } }"#)
+        .unwrap();
+    assert_eq!(config.notifications.milestones[0].minutes, 25);
+    assert!(config.notifications.notify_send);
+  }
+
   #[test]
   fn test_parse_style_default() {
     let style = parse_style("");
@@ -0,0 +1,148 @@
+use std::{
+    path::{Path, PathBuf},
+    process::Command,
+};
+
+use crate::{
+    enums::{ContentJson, SlideContentType, SlidesJson},
+    error::{io, Error, Result},
+    loaders::deck_dir,
+};
+
+/// Directory next to the slides file where rendered diagrams are cached.
+pub const CACHE_DIR: &str = ".tui-slides-cache";
+
+pub fn cache_dir(json_slides: &str) -> PathBuf {
+    deck_dir(json_slides).join(CACHE_DIR)
+}
+
+/// FNV-1a hash, stable between runs and Rust versions so cached files stay valid.
+pub fn content_hash(content: &str) -> u64 {
+    content.bytes().fold(0xcbf29ce484222325, |hash, b| {
+        (hash ^ b as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+pub fn is_diagram(item: &ContentJson) -> bool {
+    matches!(
+        item.type_,
+        SlideContentType::Mermaid | SlideContentType::Graphviz
+    )
+}
+
+/// Path of rendered diagram image for the item, file might not exist yet.
+pub fn diagram_path(item: &ContentJson, json_slides: &str) -> PathBuf {
+    let source = item.content.clone().unwrap_or_default();
+    let name = format!("{:?}-{:016x}.png", item.type_, content_hash(&source)).to_lowercase();
+    cache_dir(json_slides).join(name)
+}
+
+/// Rendered diagram image for the item if it is available in cache.
+pub fn cached_diagram(item: &ContentJson, json_slides: &str) -> Option<PathBuf> {
+    let path = diagram_path(item, json_slides);
+    path.exists().then_some(path)
+}
+
+fn render_mermaid(source: &str, output: &Path) -> Result<()> {
+    let input = output.with_extension("mmd");
+    std::fs::write(&input, source).map_err(io("failed to write diagram source"))?;
+    let status = Command::new("mmdc")
+        .arg("-i")
+        .arg(&input)
+        .arg("-o")
+        .arg(output)
+        .args(["-t", "dark", "-b", "black"])
+        .output()
+        .map_err(|e| {
+            Error::Render(format!(
+                "failed to run `mmdc`, install @mermaid-js/mermaid-cli: {}",
+                e
+            ))
+        })?;
+    let _ = std::fs::remove_file(&input);
+    if !status.status.success() {
+        return Err(Error::Render(format!(
+            "mmdc failed: {}",
+            String::from_utf8_lossy(&status.stderr)
+        )));
+    }
+    Ok(())
+}
+
+/// Graph colors default to light on black to match dark slides, DOT source can
+/// still override them.
+fn render_graphviz(source: &str, output: &Path) -> Result<()> {
+    let input = output.with_extension("dot");
+    std::fs::write(&input, source).map_err(io("failed to write diagram source"))?;
+    let status = Command::new("dot")
+        .arg("-Tpng")
+        .args(["-Gbgcolor=black", "-Gcolor=white", "-Gfontcolor=white"])
+        .args(["-Ncolor=white", "-Nfontcolor=white"])
+        .args(["-Ecolor=white", "-Efontcolor=white"])
+        .arg("-o")
+        .arg(output)
+        .arg(&input)
+        .output()
+        .map_err(|e| Error::Render(format!("failed to run `dot`, install graphviz: {}", e)))?;
+    let _ = std::fs::remove_file(&input);
+    if !status.status.success() {
+        return Err(Error::Render(format!(
+            "dot failed: {}",
+            String::from_utf8_lossy(&status.stderr)
+        )));
+    }
+    Ok(())
+}
+
+fn render_diagram(item: &ContentJson, json_slides: &str, force: bool) -> Result<bool> {
+    let output = diagram_path(item, json_slides);
+    if output.exists() && !force {
+        return Ok(false);
+    }
+    std::fs::create_dir_all(cache_dir(json_slides)).map_err(io("failed to create cache dir"))?;
+    let source = item.content.clone().unwrap_or_default();
+    match item.type_ {
+        SlideContentType::Mermaid => render_mermaid(&source, &output)?,
+        SlideContentType::Graphviz => render_graphviz(&source, &output)?,
+        _ => return Ok(false),
+    }
+    Ok(true)
+}
+
+/// Diagram items of the whole deck, including the ones inside tabs.
+pub fn deck_diagrams(slides: &SlidesJson) -> Vec<&ContentJson> {
+    slides
+        .items()
+        .into_iter()
+        .filter(|item| is_diagram(item))
+        .collect()
+}
+
+/// Outcome of rendering deck diagrams ahead of the talk.
+#[derive(Debug, Default)]
+pub struct WarmReport {
+    pub rendered: usize,
+    pub cached: usize,
+    pub failed: Vec<String>,
+}
+
+/// Render diagrams of the deck into the cache, cached ones are kept unless `force`.
+pub fn warm_cache(slides: &SlidesJson, json_slides: &str, force: bool) -> WarmReport {
+    let mut report = WarmReport::default();
+    for item in deck_diagrams(slides) {
+        match render_diagram(item, json_slides, force) {
+            Ok(true) => report.rendered += 1,
+            Ok(false) => report.cached += 1,
+            Err(e) => report.failed.push(format!("{:?}: {}", item.type_, e)),
+        }
+    }
+    report
+}
+
+/// Render all diagrams of the deck which are not cached yet. Failures are only
+/// logged, such items show error placeholder instead of the image.
+pub fn render_diagrams(slides: &SlidesJson, json_slides: &str) {
+    for e in warm_cache(slides, json_slides, false).failed {
+        log::error!("Failed to render diagram: {e}");
+    }
+}
@@ -1,8 +1,22 @@
 use image::DynamicImage;
-use ratatui::{layout::Rect, text::Line, widgets::{Block, Paragraph, Sparkline}};
+use ratatui::{layout::Rect, text::Line, widgets::{Block, Paragraph, Sparkline, Table, Tabs}};
+use std::collections::BTreeMap;
+
 use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
 use tui_big_text::BigText;
 
+use crate::{
+    action::Action,
+    widgets::{
+        clock::ClockWidget, countdown::CountdownWidget, icon::IconWidget, pie::PieChart,
+        plot::PlotWidget, qr::QrWidget, rule::RuleWidget,
+    },
+};
+
+/// Fields unknown to this version of schema, kept so decks can be written back unchanged.
+pub type ExtraFields = Map<String, Value>;
+
 // #[derive(Debug)]
 pub enum ReturnSlideWidget<'a> {
     Paragraph(Paragraph<'a>),
@@ -13,6 +27,18 @@ pub enum ReturnSlideWidget<'a> {
     Sparkline(Sparkline<'a>),
     CodeHighlight(Paragraph<'a>),
     // CodeHighlight(Line<'a>),
+    Pie(PieChart),
+    Table(Table<'a>),
+    Tabs(Tabs<'a>),
+    QrCode(QrWidget),
+    Questions,
+    Menu,
+    Icon(IconWidget),
+    Plot(PlotWidget),
+    Rule(RuleWidget),
+    Countdown(CountdownWidget),
+    Clock(ClockWidget),
+    Terminal,
 }
 
 #[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
@@ -23,18 +49,187 @@ pub enum SlideContentType {
     Image,
     Block,
     Sparkline,
-    CodeHighlight
+    CodeHighlight,
+    Pie,
+    Table,
+    Tabs,
+    QrCode,
+    Questions,
+    Mermaid,
+    Graphviz,
+    Math,
+    Icon,
+    Plot,
+    Rule,
+    Countdown,
+    Clock,
+    Terminal,
+    Diff,
+    Repl,
+    Ansi,
+    Menu,
+}
+
+/// Unit `CodeHighlight` is typed by when the slide appears.
+#[derive(Debug, PartialEq, Serialize, Deserialize, Clone)]
+pub enum Typewriter {
+    Chars,
+    Lines,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct TabJson {
+    pub title: String,
+    pub content: Vec<ContentJson>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct SliceJson {
+    pub label: String,
+    pub value: f64,
+    pub color: Option<String>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Numbered note on `line` of code (numbered like `highlight_lines`).
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct CalloutJson {
+    pub line: usize,
+    pub text: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+/// Choice of `Menu` item going to slide with `id` of `goto`.
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
+pub struct MenuOptionJson {
+    pub label: String,
+    pub goto: String,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct ContentJson {
     #[serde(rename = "type")]
     pub type_: SlideContentType,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub content: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub rect: Option<Rect>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub color: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub data: Option<Vec<u64>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub max: Option<u64>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub slices: Option<Vec<SliceJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub donut: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub language: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub header: Option<Vec<String>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rows: Option<Vec<Vec<String>>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tabs: Option<Vec<TabJson>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub scrollable: Option<bool>,
+    /// Milliseconds after slide entry before the item appears.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub delay: Option<u64>,
+    /// CSV file relative to the slides file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub csv: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<bool>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub vertical: Option<bool>,
+    /// Seconds, e.g. of `Countdown`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration: Option<u64>,
+    /// strftime-like format, e.g. of `Clock`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub format: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub big: Option<bool>,
+    /// Syntect theme of `CodeHighlight`, overrides theme of the deck.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line_numbers: Option<bool>,
+    /// Number of the first line when `line_numbers` are shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub start_line: Option<usize>,
+    /// Lines of code to emphasize, numbered from `start_line`, the rest is dimmed.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub highlight_lines: Option<Vec<usize>>,
+    /// Source file of `CodeHighlight` relative to the slides file, read on load.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Lines of `file` to show, e.g. `10..42` (inclusive), `10..` or `..42`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub lines: Option<String>,
+    /// Region of `file` between `slide:begin <name>` and `slide:end` marker comments,
+    /// `lines` are then counted within the region.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub region: Option<String>,
+    /// Interpreter running `CodeHighlight` on key press, empty for the configured one.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exec: Option<String>,
+    /// FIGlet font of `BigText` from `fonts` of the deck theme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub font: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typewriter: Option<Typewriter>,
+    /// Milliseconds per typed character or line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub typewriter_ms: Option<u64>,
+    /// Split code, paragraph or table rows longer than `rect` into generated slides.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub paginate: Option<bool>,
+    /// Wrap lines of `CodeHighlight` wider than `rect` instead of scrolling them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub wrap: Option<bool>,
+    /// Columns of tab stops in code, 4 by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tab_width: Option<usize>,
+    /// Strip indentation common to all lines of code, on by default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub dedent: Option<bool>,
+    /// Title bar of `CodeHighlight`, empty one shows the name of `file`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Fill of `CodeHighlight`, color or `theme` for the background of code theme.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub background: Option<String>,
+    /// Rows between `CodeHighlight` frame and code, twice as many columns on sides.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub padding: Option<u16>,
+    /// Seconds between runs of `CodeHighlight` with `exec`, which is then run as soon
+    /// as it is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub refresh_secs: Option<u64>,
+    /// Last lines of `CodeHighlight` revealed by Next one step at a time before the
+    /// whole code, empty list reveals it line by line.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub steps: Option<Vec<usize>>,
+    /// Notes of `CodeHighlight` lines, marked by numbers and listed below the code.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub callouts: Option<Vec<CalloutJson>>,
+    /// `id` of slide to go to when the item is clicked or followed with Enter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub goto: Option<String>,
+    /// Choices of `Menu`, picked with Up / Down and followed with Enter.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub options: Option<Vec<MenuOptionJson>>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
 impl Default for ContentJson {
@@ -46,24 +241,178 @@ impl Default for ContentJson {
             color: None,
             data: None,
             max: None,
+            slices: None,
+            donut: None,
+            language: None,
+            header: None,
+            rows: None,
+            tabs: None,
+            scrollable: None,
+            delay: None,
+            csv: None,
+            line: None,
+            vertical: None,
+            duration: None,
+            format: None,
+            big: None,
+            theme: None,
+            line_numbers: None,
+            start_line: None,
+            highlight_lines: None,
+            file: None,
+            lines: None,
+            region: None,
+            exec: None,
+            font: None,
+            typewriter: None,
+            typewriter_ms: None,
+            paginate: None,
+            wrap: None,
+            tab_width: None,
+            dedent: None,
+            title: None,
+            background: None,
+            padding: None,
+            refresh_secs: None,
+            steps: None,
+            callouts: None,
+            goto: None,
+            options: None,
+            extra: ExtraFields::new(),
         }
     }
 }
 
 #[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct SlideJson {
+    #[serde(skip_serializing_if = "Option::is_none")]
     pub title: Option<String>,
+    /// Name of slide used by `goto` of items and `GotoId` keys.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<String>,
     pub content: Vec<ContentJson>,
+    /// Time budget of the slide, presenter view compares the talk clock with it.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub duration_secs: Option<u64>,
+    /// Speaker notes, shown only in the notes pane.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub notes: Option<String>,
+    /// Audio file relative to the slides file, played while the slide is shown.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub audio: Option<String>,
+    /// Key bindings active only on this slide, e.g. `"<d>": { "RunHook": "./deploy.sh" }`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<BTreeMap<String, Action>>,
+    /// Next and Previous leave the slide only when pressed twice, for live demos.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub confirm_advance: Option<bool>,
+    /// Backup slide skipped by Next and Previous, reached only by going to it directly.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub hidden: Option<bool>,
+    /// Vertical sub-slides of this section slide, Up and Down move between them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub children: Option<Vec<SlideJson>>,
+    /// Section and sub-slide number (0 for the section slide) in decks with `children`,
+    /// set when sub-slides are put after their section on load.
+    #[serde(skip)]
+    pub section: Option<(usize, usize)>,
+    /// Index of the slide in the file and number of its sub-slide (0 for the slide
+    /// itself), edit mode changes the deck as written through it.
+    #[serde(skip)]
+    pub origin: (usize, usize),
+    #[serde(flatten)]
+    pub extra: ExtraFields,
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+impl SlideJson {
+    pub fn new(title: Option<String>, content: Vec<ContentJson>) -> Self {
+        Self {
+            title,
+            id: None,
+            content,
+            duration_secs: None,
+            notes: None,
+            audio: None,
+            keys: None,
+            confirm_advance: None,
+            hidden: None,
+            children: None,
+            section: None,
+            origin: (0, 0),
+            extra: ExtraFields::new(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct BoxSizeJson {
     pub width: u16,
     pub height: u16,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl BoxSizeJson {
+    pub fn new(width: u16, height: u16) -> Self {
+        Self {
+            width,
+            height,
+            extra: ExtraFields::new(),
+        }
+    }
 }
 
-#[derive(Debug, Serialize, Deserialize, PartialEq)]
+#[derive(Debug, Serialize, Deserialize, PartialEq, Clone)]
 pub struct SlidesJson {
     pub box_size: BoxSizeJson,
     pub slides: Vec<SlideJson>,
+    /// Syntect theme of all code blocks in the deck.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub code_theme: Option<String>,
+    /// Directory with theme assets (palette, borders, fonts) relative to the slides file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// Language tag like `de` or `en-GB`, number and date format of data widgets.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub locale: Option<String>,
+    /// Folder of `.sublime-syntax` files relative to the slides file.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub syntaxes: Option<String>,
+    /// Key bindings of the whole deck, `keys` of a slide win over them.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub keys: Option<BTreeMap<String, Action>>,
+    #[serde(flatten)]
+    pub extra: ExtraFields,
+}
+
+impl SlidesJson {
+    pub fn new(box_size: BoxSizeJson, slides: Vec<SlideJson>) -> Self {
+        Self {
+            box_size,
+            slides,
+            code_theme: None,
+            theme: None,
+            locale: None,
+            syntaxes: None,
+            keys: None,
+            extra: ExtraFields::new(),
+        }
+    }
+
+    /// Items of the whole deck, including the ones inside tabs.
+    pub fn items(&self) -> Vec<&ContentJson> {
+        fn collect<'a>(content: &'a [ContentJson], items: &mut Vec<&'a ContentJson>) {
+            for item in content {
+                items.push(item);
+                for tab in item.tabs.iter().flatten() {
+                    collect(&tab.content, items);
+                }
+            }
+        }
+        let mut items = vec![];
+        for slide in &self.slides {
+            collect(&slide.content, &mut items);
+        }
+        items
+    }
 }
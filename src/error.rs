@@ -0,0 +1,57 @@
+use std::io;
+
+use thiserror::Error;
+
+/// Errors returned by the library, so embedders can recover instead of the process
+/// aborting on a broken deck.
+#[derive(Debug, Error)]
+pub enum Error {
+    #[error("{context}")]
+    Io {
+        context: String,
+        #[source]
+        source: io::Error,
+    },
+    #[error("parse error: {0}")]
+    Parse(String),
+    #[error("failed to load image '{path}'")]
+    Image {
+        path: String,
+        #[source]
+        source: image::ImageError,
+    },
+    #[error("render error: {0}")]
+    Render(String),
+    #[error("terminal protocol error: {0}")]
+    Protocol(String),
+}
+
+pub type Result<T, E = Error> = std::result::Result<T, E>;
+
+impl From<io::Error> for Error {
+    fn from(source: io::Error) -> Self {
+        Self::Io {
+            context: String::from("io error"),
+            source,
+        }
+    }
+}
+
+impl Error {
+    /// Message including all underlying causes, for showing the error in a slide.
+    pub fn report(&self) -> String {
+        let mut message = self.to_string();
+        let mut source = std::error::Error::source(self);
+        while let Some(cause) = source {
+            message.push_str(&format!(": {}", cause));
+            source = cause.source();
+        }
+        message
+    }
+}
+
+/// Io error with description what was being done, for `map_err`.
+pub fn io(context: impl Into<String>) -> impl FnOnce(io::Error) -> Error {
+    let context = context.into();
+    move |source| Error::Io { context, source }
+}
@@ -0,0 +1,212 @@
+use std::{
+    collections::HashMap,
+    io::Write,
+    path::Path,
+    process::Stdio,
+    sync::{Arc, Mutex},
+    time::Instant,
+};
+
+use tempfile::TempPath;
+use tokio::{process::Command, task::JoinHandle};
+
+/// State of code block run, shared with the task running it.
+#[derive(Debug, Clone, PartialEq)]
+pub enum ExecState {
+    Running,
+    Done {
+        stdout: String,
+        stderr: String,
+        status: Option<i32>,
+    },
+    Failed(String),
+}
+
+/// Running or finished code block, the process is killed when dropped.
+pub struct ExecRun {
+    state: Arc<Mutex<ExecState>>,
+    task: JoinHandle<()>,
+    started: Instant,
+    /// Output of the run this one refreshes, shown until this one is done.
+    previous: Option<ExecState>,
+}
+
+impl ExecRun {
+    /// State to show, output of the refreshed run while this one is running.
+    pub fn state(&self) -> ExecState {
+        match (self.current(), &self.previous) {
+            (ExecState::Running, Some(previous)) => previous.clone(),
+            (state, _) => state,
+        }
+    }
+
+    fn current(&self) -> ExecState {
+        self.state
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or(ExecState::Running)
+    }
+
+    pub fn is_done(&self) -> bool {
+        self.current() != ExecState::Running
+    }
+
+    pub fn started(&self) -> Instant {
+        self.started
+    }
+
+    /// Keep showing output of `run` until this run is done.
+    pub fn refreshing(mut self, run: &ExecRun) -> Self {
+        self.previous = Some(run.state());
+        self
+    }
+}
+
+impl Drop for ExecRun {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// Interpreter command of code block, empty `exec` of item means the one configured
+/// for its language.
+pub fn interpreter(
+    exec: &str,
+    language: Option<&str>,
+    interpreters: &HashMap<String, String>,
+) -> Option<String> {
+    if !exec.trim().is_empty() {
+        return Some(exec.to_string());
+    }
+    interpreters.get(language?).cloned()
+}
+
+/// Shell command running `file`, which is put in place of `{file}` or appended.
+fn command_line(interpreter: &str, file: &Path) -> String {
+    let file = file.to_string_lossy();
+    if interpreter.contains("{file}") {
+        interpreter.replace("{file}", &file)
+    } else {
+        format!("{} '{}'", interpreter, file)
+    }
+}
+
+/// Code written to new file of random name in the temporary directory, so other
+/// users can neither plant nor swap it. The file is removed when dropped.
+fn script_file(code: &str, language: Option<&str>) -> std::io::Result<TempPath> {
+    let mut file = tempfile::Builder::new()
+        .prefix("tui-slides-")
+        .suffix(&format!(".{}", language.unwrap_or("txt")))
+        .tempfile()?;
+    file.write_all(code.as_bytes())?;
+    Ok(file.into_temp_path())
+}
+
+/// Run hook command of slide key in `cwd`, failures are only logged.
+pub fn run_hook(command: &str, cwd: &Path) {
+    let mut child = match Command::new("sh")
+        .arg("-c")
+        .arg(command)
+        .current_dir(cwd)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+    {
+        Ok(child) => child,
+        Err(e) => {
+            log::error!("Failed to run hook `{command}`: {e}");
+            return;
+        }
+    };
+    let command = command.to_string();
+    tokio::spawn(async move {
+        match child.wait().await {
+            Ok(status) if !status.success() => log::error!("Hook `{command}` failed: {status}"),
+            Err(e) => log::error!("Failed to wait for hook `{command}`: {e}"),
+            _ => {}
+        }
+    });
+}
+
+/// Write the code to temporary file and run it with `interpreter` in `cwd`.
+pub fn run(code: &str, language: Option<&str>, interpreter: &str, cwd: &Path) -> ExecRun {
+    let state = Arc::new(Mutex::new(ExecState::Running));
+    let file = script_file(code, language);
+    let (interpreter, cwd, task_state) =
+        (interpreter.to_string(), cwd.to_path_buf(), state.clone());
+    let task = tokio::spawn(async move {
+        let result = match file {
+            Ok(file) => {
+                let command = command_line(&interpreter, &file);
+                Command::new("sh")
+                    .arg("-c")
+                    .arg(&command)
+                    .current_dir(cwd)
+                    .stdin(Stdio::null())
+                    .kill_on_drop(true)
+                    .output()
+                    .await
+                    .map(|output| ExecState::Done {
+                        stdout: String::from_utf8_lossy(&output.stdout).to_string(),
+                        stderr: String::from_utf8_lossy(&output.stderr).to_string(),
+                        status: output.status.code(),
+                    })
+                    .unwrap_or_else(|e| {
+                        ExecState::Failed(format!("failed to run `{}`: {}", command, e))
+                    })
+            }
+            Err(e) => ExecState::Failed(format!("failed to write script file: {}", e)),
+        };
+        if let Ok(mut state) = task_state.lock() {
+            *state = result;
+        }
+    });
+    ExecRun {
+        state,
+        task,
+        started: Instant::now(),
+        previous: None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_interpreter_and_command_line() {
+        let interpreters = HashMap::from([("py".to_string(), "python3".to_string())]);
+        assert_eq!(
+            interpreter("", Some("py"), &interpreters),
+            Some("python3".to_string())
+        );
+        assert_eq!(
+            interpreter("node", Some("py"), &interpreters),
+            Some("node".to_string())
+        );
+        assert_eq!(interpreter("", Some("rs"), &interpreters), None);
+
+        let file = Path::new("/tmp/a.rs");
+        assert_eq!(command_line("python3", file), "python3 '/tmp/a.rs'");
+        assert_eq!(
+            command_line("rustc {file} -o /tmp/a && /tmp/a", file),
+            "rustc /tmp/a.rs -o /tmp/a && /tmp/a"
+        );
+    }
+
+    #[test]
+    fn test_script_file() {
+        let first = script_file("print(1)", Some("py")).unwrap();
+        let second = script_file("print(1)", Some("py")).unwrap();
+        assert_ne!(first.to_path_buf(), second.to_path_buf());
+        assert_eq!(first.extension(), Some("py".as_ref()));
+        assert_eq!(std::fs::read_to_string(&first).unwrap(), "print(1)");
+
+        let path = first.to_path_buf();
+        drop(first);
+        assert!(!path.exists());
+    }
+}
@@ -0,0 +1,950 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use ratatui::{
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+};
+use syntect::{
+    easy::HighlightLines,
+    highlighting::{Theme, ThemeSet},
+    parsing::{SyntaxSet, SyntaxSetBuilder},
+    util::LinesWithEndings,
+};
+use syntect_tui::into_span;
+
+use crate::{
+    capabilities::capabilities,
+    diagrams::content_hash,
+    enums::{CalloutJson, ContentJson, SlideContentType, SlidesJson, Typewriter},
+    error::{Error, Result},
+};
+
+pub const DEFAULT_CODE_THEME: &str = "base16-ocean.dark";
+pub const DEFAULT_LIGHT_CODE_THEME: &str = "InspiredGitHub";
+pub const DEFAULT_TAB_WIDTH: usize = 4;
+
+pub type HighlightedLines = Arc<Vec<Line<'static>>>;
+
+/// Language token of code without `language`: interpreter of shebang line, extension
+/// of the source `file`, then keywords the code starts with.
+pub fn detect_language(code: &str, file: Option<&str>) -> Option<String> {
+    let first = code
+        .lines()
+        .find(|l| !l.trim().is_empty())
+        .unwrap_or_default();
+    if let Some(shebang) = first.strip_prefix("#!") {
+        let mut words = shebang.split_whitespace();
+        let mut program = words.next()?.rsplit('/').next()?;
+        if program == "env" {
+            program = words.find(|w| !w.starts_with('-'))?;
+        }
+        let program = program.trim_end_matches(|c: char| c.is_ascii_digit() || c == '.');
+        let language = match program {
+            "python" => "py",
+            "node" | "deno" | "bun" => "js",
+            "ruby" => "rb",
+            "perl" => "pl",
+            "bash" | "sh" | "zsh" | "dash" => "sh",
+            other => other,
+        };
+        return Some(language.to_string());
+    }
+    if let Some(extension) = file.and_then(|f| Path::new(f).extension()) {
+        return extension.to_str().map(str::to_string);
+    }
+    let first = first.trim_start();
+    let starts = |prefixes: &[&str]| prefixes.iter().any(|p| first.starts_with(p));
+    let language = if starts(&[
+        "fn ", "use ", "pub ", "impl ", "struct ", "#[", "mod ", "let mut ",
+    ]) {
+        "rs"
+    } else if starts(&["def ", "import ", "from ", "class ", "print("]) {
+        "py"
+    } else if starts(&["package ", "func "]) {
+        "go"
+    } else if starts(&["#include", "int main"]) {
+        "c"
+    } else if starts(&["function ", "const ", "console.", "export ", "require("]) {
+        "js"
+    } else if starts(&["<!DOCTYPE", "<html", "<div"]) {
+        "html"
+    } else if starts(&["SELECT ", "select ", "CREATE ", "INSERT "]) {
+        "sql"
+    } else if starts(&["$ ", "echo ", "cd "]) {
+        "sh"
+    } else if starts(&["{", "["]) {
+        "json"
+    } else {
+        return None;
+    };
+    Some(language.to_string())
+}
+
+/// Tabs replaced with spaces up to the next tab stop.
+pub fn expand_tabs(code: &str, tab_width: usize) -> String {
+    if !code.contains('\t') {
+        return code.to_string();
+    }
+    let tab_width = tab_width.max(1);
+    let lines: Vec<String> = code
+        .split('\n')
+        .map(|line| {
+            let mut expanded = String::new();
+            let mut column = 0;
+            for c in line.chars() {
+                if c == '\t' {
+                    let spaces = tab_width - column % tab_width;
+                    expanded.push_str(&" ".repeat(spaces));
+                    column += spaces;
+                } else {
+                    expanded.push(c);
+                    column += 1;
+                }
+            }
+            expanded
+        })
+        .collect();
+    lines.join("\n")
+}
+
+/// Code without indentation common to all its non-blank lines, e.g. of a snippet
+/// taken from the middle of nested code.
+pub fn dedent(code: &str) -> String {
+    let indent = code
+        .split('\n')
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start_matches(' ').len())
+        .min()
+        .unwrap_or(0);
+    if indent == 0 {
+        return code.to_string();
+    }
+    let lines: Vec<&str> = code
+        .split('\n')
+        .map(|l| l.get(indent..).unwrap_or_else(|| l.trim_start()))
+        .collect();
+    lines.join("\n")
+}
+
+/// Code item with everything its highlighted output depends on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct CodeBlock {
+    pub code: String,
+    pub language: Option<String>,
+    /// Theme names in order of priority, first known one is used.
+    pub themes: Vec<String>,
+}
+
+impl CodeBlock {
+    /// Themes are tried in order: item, command line, deck.
+    pub fn new(item: &ContentJson, cli_theme: Option<&str>, deck_theme: Option<&str>) -> Self {
+        let themes = [item.theme.as_deref(), cli_theme, deck_theme]
+            .into_iter()
+            .flatten()
+            .map(str::to_string)
+            .collect();
+        let code = item.content.clone().unwrap_or_default();
+        let code = expand_tabs(&code, item.tab_width.unwrap_or(DEFAULT_TAB_WIDTH));
+        let code = match item.dedent {
+            Some(false) => code,
+            _ => dedent(&code),
+        };
+        let language = item
+            .language
+            .clone()
+            .or_else(|| detect_language(&code, item.file.as_deref()));
+        Self {
+            code,
+            language,
+            themes,
+        }
+    }
+
+    /// Cache key, changes whenever output of the block would.
+    pub fn key(&self) -> u64 {
+        let language = self.language.as_deref().unwrap_or_default();
+        content_hash(&format!(
+            "{}\0{}\0{}",
+            language,
+            self.themes.join("\0"),
+            self.code
+        ))
+    }
+
+    /// Theme of the block, unknown names fall back to the default theme for the
+    /// terminal background.
+    pub fn theme<'a>(&self, themes: &'a ThemeSet) -> &'a Theme {
+        let default = if capabilities().light {
+            DEFAULT_LIGHT_CODE_THEME
+        } else {
+            DEFAULT_CODE_THEME
+        };
+        self.themes
+            .iter()
+            .find_map(|name| themes.themes.get(name))
+            .unwrap_or_else(|| &themes.themes[default])
+    }
+
+    pub fn highlight(&self, syntaxes: &SyntaxSet, themes: &ThemeSet) -> Vec<Line<'static>> {
+        let syntax = self
+            .language
+            .as_ref()
+            .and_then(|lang| syntaxes.find_syntax_by_token(lang))
+            .or_else(|| syntaxes.find_syntax_by_first_line(&self.code))
+            .unwrap_or_else(|| syntaxes.find_syntax_plain_text());
+        let mut h = HighlightLines::new(syntax, self.theme(themes));
+
+        let mut lines = vec![];
+        for c_line in self.code.split('\n') {
+            for line in LinesWithEndings::from(c_line) {
+                let spans: Vec<Span<'static>> = h
+                    .highlight_line(line, syntaxes)
+                    .unwrap_or_default()
+                    .into_iter()
+                    .filter_map(|seg| into_span(seg).ok())
+                    .map(|span| Span::styled(span.content.into_owned(), span.style))
+                    .collect();
+                lines.push(Line::from(spans));
+            }
+        }
+        lines
+    }
+}
+
+/// Columns taken by line numbers of `count` lines starting at `start`, separator
+/// included.
+pub fn gutter_width(start: usize, count: usize) -> usize {
+    let last = start + count.saturating_sub(1);
+    last.to_string().len() + " │ ".chars().count()
+}
+
+/// Widest line of code in columns, `gutter` columns are added to every line.
+pub fn code_width(code: &str, gutter: usize) -> usize {
+    code.split('\n')
+        .map(|line| Line::from(line).width() + gutter)
+        .max()
+        .unwrap_or(0)
+}
+
+/// Rows the code takes in `width` columns, wrapped lines take more than one.
+pub fn code_rows(code: &str, gutter: usize, width: usize, wrap: bool) -> usize {
+    let lines = code.split('\n');
+    if !wrap || width == 0 {
+        return lines.count();
+    }
+    lines
+        .map(|line| (Line::from(line).width() + gutter).div_ceil(width).max(1))
+        .sum()
+}
+
+/// Lines prefixed with right aligned numbers starting at `start` and a separator,
+/// so copied code stays readable.
+pub fn number_lines(lines: Vec<Line<'static>>, start: usize) -> Vec<Line<'static>> {
+    let last = start + lines.len().saturating_sub(1);
+    let width = last.to_string().len();
+    let gutter = Style::default().fg(Color::Rgb(100, 100, 100));
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, line)| {
+            let number = Span::styled(format!("{:>width$} │ ", start + i), gutter);
+            let mut spans = vec![number];
+            spans.extend(line.spans);
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Number of lines shown at each step of code with `count` lines, `steps` are the last
+/// lines (numbered from `start`) of the steps before the whole code, none means one
+/// line per step.
+pub fn step_line_counts(steps: &[usize], start: usize, count: usize) -> Vec<usize> {
+    let mut counts: Vec<usize> = if steps.is_empty() {
+        (1..count).collect()
+    } else {
+        steps
+            .iter()
+            .map(|line| (line + 1).saturating_sub(start).min(count))
+            .collect()
+    };
+    counts.push(count);
+    counts.dedup();
+    counts
+}
+
+/// Listed lines (numbered from `start`) are made bold and all the others dimmed.
+pub fn emphasize_lines(
+    lines: Vec<Line<'static>>,
+    start: usize,
+    highlighted: &[usize],
+) -> Vec<Line<'static>> {
+    lines
+        .into_iter()
+        .enumerate()
+        .map(|(i, mut line)| {
+            let modifier = if highlighted.contains(&(start + i)) {
+                Modifier::BOLD
+            } else {
+                Modifier::DIM
+            };
+            for span in line.spans.iter_mut() {
+                span.style = span.style.add_modifier(modifier);
+            }
+            line
+        })
+        .collect()
+}
+
+fn callout_marker(number: usize) -> Span<'static> {
+    Span::styled(
+        format!(" {} ", number),
+        Style::default().fg(Color::Black).bg(Color::Yellow),
+    )
+}
+
+/// Lines (numbered from `start`) with callouts get the number of the callout at the end.
+pub fn mark_callouts(
+    mut lines: Vec<Line<'static>>,
+    start: usize,
+    callouts: &[CalloutJson],
+) -> Vec<Line<'static>> {
+    for (i, callout) in callouts.iter().enumerate() {
+        let Some(line) = callout
+            .line
+            .checked_sub(start)
+            .and_then(|index| lines.get_mut(index))
+        else {
+            continue;
+        };
+        line.spans.push(Span::raw(" "));
+        line.spans.push(callout_marker(i + 1));
+    }
+    lines
+}
+
+/// Legend of callouts on the first `shown` lines (numbered from `start`), so notes of
+/// lines not revealed yet stay hidden.
+pub fn callout_legend(callouts: &[CalloutJson], start: usize, shown: usize) -> Vec<Line<'static>> {
+    callouts
+        .iter()
+        .enumerate()
+        .filter(|(_, c)| c.line >= start && c.line - start < shown)
+        .map(|(i, c)| {
+            Line::from(vec![
+                callout_marker(i + 1),
+                Span::raw(format!(" {}", c.text)),
+            ])
+        })
+        .collect()
+}
+
+/// Lines of prose with `` `code` `` spans drawn in `code_style` without the backticks,
+/// unpaired backtick is kept as it is.
+pub fn inline_code_lines(text: &str, code_style: Style) -> Vec<Line<'static>> {
+    text.split('\n')
+        .map(|line| {
+            let parts: Vec<&str> = line.split('`').collect();
+            // -- odd count of parts means every backtick has its pair
+            let paired = parts.len() % 2 == 1;
+            let mut spans = vec![];
+            for (i, part) in parts.iter().enumerate() {
+                let is_code = i % 2 == 1 && (paired || i + 1 < parts.len());
+                if is_code {
+                    spans.push(Span::styled(part.to_string(), code_style));
+                } else if i % 2 == 1 {
+                    spans.push(Span::raw(format!("`{}", part)));
+                } else if !part.is_empty() {
+                    spans.push(Span::raw(part.to_string()));
+                }
+            }
+            Line::from(spans)
+        })
+        .collect()
+}
+
+/// Lines of diff colored by kind: added green, removed red, hunk headers cyan and file
+/// headers bold. Lines before the first hunk of unified diff are file headers, so
+/// `--- a/file` is not taken for removed line.
+pub fn diff_lines(diff: &str) -> Vec<Line<'static>> {
+    let unified = diff.lines().any(|l| l.starts_with("@@"));
+    let mut in_hunk = !unified;
+    diff.split('\n')
+        .map(|line| {
+            let style = if line.starts_with("@@") {
+                in_hunk = true;
+                Style::default().fg(Color::Cyan)
+            } else if unified && line.starts_with("diff ") {
+                in_hunk = false;
+                Style::default().add_modifier(Modifier::BOLD)
+            } else if !in_hunk {
+                Style::default().add_modifier(Modifier::BOLD)
+            } else if line.starts_with('+') {
+                Style::default().fg(Color::Green)
+            } else if line.starts_with('-') {
+                Style::default().fg(Color::Red)
+            } else if line.starts_with('\\') {
+                Style::default().fg(Color::Rgb(100, 100, 100))
+            } else {
+                Style::default()
+            };
+            Line::styled(line.to_string(), style)
+        })
+        .collect()
+}
+
+/// Color of SGR parameters `38;5;n` or `38;2;r;g;b` (and `48;...`) after the first one.
+fn extended_color(params: &mut impl Iterator<Item = u16>) -> Option<Color> {
+    match params.next()? {
+        5 => Some(Color::Indexed(params.next()? as u8)),
+        2 => {
+            let (r, g, b) = (params.next()?, params.next()?, params.next()?);
+            Some(Color::Rgb(r as u8, g as u8, b as u8))
+        }
+        _ => None,
+    }
+}
+
+/// Style after SGR sequence `ESC [ params m`, unknown parameters are ignored.
+fn sgr_style(style: Style, params: &str) -> Style {
+    let mut params = params.split(';').map(|p| p.parse::<u16>().unwrap_or(0));
+    let mut style = style;
+    while let Some(param) = params.next() {
+        style = match param {
+            0 => Style::default(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            22 => style.remove_modifier(Modifier::BOLD | Modifier::DIM),
+            23 => style.remove_modifier(Modifier::ITALIC),
+            24 => style.remove_modifier(Modifier::UNDERLINED),
+            27 => style.remove_modifier(Modifier::REVERSED),
+            29 => style.remove_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(Color::Indexed(param as u8 - 30)),
+            90..=97 => style.fg(Color::Indexed(param as u8 - 90 + 8)),
+            40..=47 => style.bg(Color::Indexed(param as u8 - 40)),
+            100..=107 => style.bg(Color::Indexed(param as u8 - 100 + 8)),
+            38 => extended_color(&mut params).map_or(style, |c| style.fg(c)),
+            48 => extended_color(&mut params).map_or(style, |c| style.bg(c)),
+            39 => Style { fg: None, ..style },
+            49 => Style { bg: None, ..style },
+            _ => style,
+        };
+    }
+    style
+}
+
+/// Lines of text with ANSI escapes, e.g. captured `cargo test --color=always`, styled
+/// by its SGR colors and attributes. Other escape sequences and carriage returns are
+/// dropped.
+pub fn ansi_lines(text: &str) -> Vec<Line<'static>> {
+    let mut style = Style::default();
+    let mut lines = vec![];
+    for line in text.split('\n') {
+        let mut spans = vec![];
+        let mut span = String::new();
+        let mut chars = line.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\u{1b}' => {
+                    if !span.is_empty() {
+                        spans.push(Span::styled(std::mem::take(&mut span), style));
+                    }
+                    match chars.next() {
+                        Some('[') => {
+                            let mut params = String::new();
+                            for c in chars.by_ref() {
+                                if ('@'..='~').contains(&c) {
+                                    if c == 'm' {
+                                        style = sgr_style(style, &params);
+                                    }
+                                    break;
+                                }
+                                params.push(c);
+                            }
+                        }
+                        // -- OSC, e.g. hyperlinks, ends with BEL or `ESC \`
+                        Some(']') => {
+                            while let Some(c) = chars.next() {
+                                if c == '\u{7}'
+                                    || (c == '\u{1b}' && chars.next_if_eq(&'\\').is_some())
+                                {
+                                    break;
+                                }
+                            }
+                        }
+                        _ => {}
+                    }
+                }
+                '\r' => {}
+                c => span.push(c),
+            }
+        }
+        if !span.is_empty() {
+            spans.push(Span::styled(span, style));
+        }
+        lines.push(Line::from(spans));
+    }
+    lines
+}
+
+/// Number of characters (line breaks included) or lines typed until `code` is shown
+/// whole.
+pub fn typewriter_units(code: &str, typewriter: &Typewriter) -> usize {
+    match typewriter {
+        Typewriter::Chars => code.chars().count(),
+        Typewriter::Lines => code.split('\n').count(),
+    }
+}
+
+/// First `units` characters or lines of code, styles of typed text are kept.
+pub fn type_lines(
+    lines: Vec<Line<'static>>,
+    typewriter: &Typewriter,
+    units: usize,
+) -> Vec<Line<'static>> {
+    if *typewriter == Typewriter::Lines {
+        return lines.into_iter().take(units).collect();
+    }
+    let mut left = units;
+    let mut typed = vec![];
+    for line in lines {
+        let mut spans = vec![];
+        for span in line.spans {
+            if left == 0 {
+                break;
+            }
+            let count = span.content.chars().count();
+            if count <= left {
+                left -= count;
+                spans.push(span);
+            } else {
+                let content: String = span.content.chars().take(left).collect();
+                left = 0;
+                spans.push(Span::styled(content, span.style));
+            }
+        }
+        typed.push(Line::from(spans));
+        // -- line break is typed too
+        if left == 0 {
+            break;
+        }
+        left -= 1;
+    }
+    typed
+}
+
+/// Code blocks of the whole deck.
+pub fn deck_code_blocks(slides: &SlidesJson, cli_theme: Option<&str>) -> Vec<CodeBlock> {
+    slides
+        .items()
+        .into_iter()
+        .filter(|item| item.type_ == SlideContentType::CodeHighlight)
+        .map(|item| CodeBlock::new(item, cli_theme, slides.code_theme.as_deref()))
+        .collect()
+}
+
+/// Default syntaxes with the ones from `.sublime-syntax` files of given folders.
+pub fn load_syntaxes(dirs: &[PathBuf]) -> Result<SyntaxSet> {
+    let builder = SyntaxSet::load_defaults_newlines().into_builder();
+    Ok(add_syntax_dirs(builder, dirs)?.build())
+}
+
+fn add_syntax_dirs(mut builder: SyntaxSetBuilder, dirs: &[PathBuf]) -> Result<SyntaxSetBuilder> {
+    for dir in dirs {
+        builder.add_from_folder(dir, true).map_err(|e| {
+            Error::Parse(format!(
+                "failed to load syntaxes '{}': {}",
+                dir.display(),
+                e
+            ))
+        })?;
+    }
+    Ok(builder)
+}
+
+/// Highlighted code shared between draw and the tasks warming it, syntaxes and
+/// themes are loaded once when the cache is created.
+#[derive(Debug, Clone)]
+pub struct HighlightCache {
+    lines: Arc<Mutex<HashMap<u64, HighlightedLines>>>,
+    /// Keys of blocks highlighted on background tasks right now.
+    pending: Arc<Mutex<HashSet<u64>>>,
+    /// Default syntaxes with the ones from `syntax_dirs`.
+    syntaxes: Arc<Mutex<Arc<SyntaxSet>>>,
+    syntax_dirs: Arc<Mutex<Vec<PathBuf>>>,
+    themes: Arc<ThemeSet>,
+}
+
+impl Default for HighlightCache {
+    fn default() -> Self {
+        Self {
+            lines: Arc::default(),
+            pending: Arc::default(),
+            syntaxes: Arc::new(Mutex::new(Arc::new(SyntaxSet::load_defaults_newlines()))),
+            syntax_dirs: Arc::default(),
+            themes: Arc::new(ThemeSet::load_defaults()),
+        }
+    }
+}
+
+impl HighlightCache {
+    /// Load custom syntax folders, cached lines are dropped when the folders change.
+    pub fn set_syntax_dirs(&self, dirs: Vec<PathBuf>) -> Result<()> {
+        let (Ok(mut syntax_dirs), Ok(mut syntaxes)) =
+            (self.syntax_dirs.lock(), self.syntaxes.lock())
+        else {
+            return Ok(());
+        };
+        if *syntax_dirs == dirs {
+            return Ok(());
+        }
+        *syntaxes = Arc::new(load_syntaxes(&dirs)?);
+        *syntax_dirs = dirs;
+        if let Ok(mut cache) = self.lines.lock() {
+            cache.clear();
+        }
+        Ok(())
+    }
+
+    fn syntax_set(&self) -> Arc<SyntaxSet> {
+        self.syntaxes
+            .lock()
+            .map(|s| s.clone())
+            .unwrap_or_else(|_| Arc::new(SyntaxSet::load_defaults_newlines()))
+    }
+
+    pub fn get(&self, block: &CodeBlock) -> Option<HighlightedLines> {
+        self.lines.lock().ok()?.get(&block.key()).cloned()
+    }
+
+    /// Background color of the code theme of the block.
+    pub fn background(&self, block: &CodeBlock) -> Option<Color> {
+        let color = block.theme(&self.themes).settings.background?;
+        Some(Color::Rgb(color.r, color.g, color.b))
+    }
+
+    /// Cached lines of the block, highlighted right away on cache miss.
+    pub fn get_or_highlight(&self, block: &CodeBlock) -> HighlightedLines {
+        if let Some(lines) = self.get(block) {
+            return lines;
+        }
+        let lines = Arc::new(block.highlight(&self.syntax_set(), &self.themes));
+        self.insert(block.key(), lines.clone());
+        lines
+    }
+
+    /// Number of cached blocks and approximate size of their lines.
+    pub fn usage(&self) -> (usize, usize) {
+        let Ok(cache) = self.lines.lock() else {
+            return (0, 0);
+        };
+        let bytes = cache
+            .values()
+            .flat_map(|lines| lines.iter())
+            .map(|line| {
+                let spans: usize = line.spans.iter().map(|s| s.content.len()).sum();
+                std::mem::size_of::<Line>() + spans + line.spans.len() * std::mem::size_of::<Span>()
+            })
+            .sum();
+        (cache.len(), bytes)
+    }
+
+    fn insert(&self, key: u64, lines: HighlightedLines) {
+        if let Ok(mut cache) = self.lines.lock() {
+            cache.insert(key, lines);
+        }
+    }
+
+    /// Drop output of blocks no longer in the deck and highlight the new or changed
+    /// ones in parallel.
+    pub fn warm(&self, blocks: Vec<CodeBlock>) {
+        let keys: HashSet<u64> = blocks.iter().map(CodeBlock::key).collect();
+        if let Ok(mut cache) = self.lines.lock() {
+            cache.retain(|key, _| keys.contains(key));
+        }
+        self.highlight_in_background(blocks);
+    }
+
+    /// Highlight blocks missing from cache, every block on its own blocking task.
+    /// Blocks already being highlighted are skipped.
+    pub fn highlight_in_background(&self, blocks: Vec<CodeBlock>) {
+        let blocks: Vec<CodeBlock> = match (self.lines.lock(), self.pending.lock()) {
+            (Ok(cache), Ok(mut pending)) => blocks
+                .into_iter()
+                .filter(|b| !cache.contains_key(&b.key()) && pending.insert(b.key()))
+                .collect(),
+            _ => return,
+        };
+        if blocks.is_empty() {
+            return;
+        }
+        let syntaxes = self.syntax_set();
+        for block in blocks {
+            let (cache, syntaxes) = (self.clone(), syntaxes.clone());
+            tokio::task::spawn_blocking(move || {
+                let lines = block.highlight(&syntaxes, &cache.themes);
+                cache.insert(block.key(), Arc::new(lines));
+                if let Ok(mut pending) = cache.pending.lock() {
+                    pending.remove(&block.key());
+                }
+            });
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_code_block_key_and_theme() {
+        let item = ContentJson {
+            type_: SlideContentType::CodeHighlight,
+            content: Some(String::from("fn main() {}\nlet x = 1;")),
+            language: Some(String::from("rs")),
+            theme: Some(String::from("unknown")),
+            ..Default::default()
+        };
+        let block = CodeBlock::new(&item, Some("InspiredGitHub"), None);
+        let themes = ThemeSet::load_defaults();
+        assert_eq!(block.theme(&themes).name.as_deref(), Some("GitHub"));
+        assert_eq!(
+            block.key(),
+            CodeBlock::new(&item, Some("InspiredGitHub"), None).key()
+        );
+        assert_ne!(block.key(), CodeBlock::new(&item, None, None).key());
+
+        let cache = HighlightCache::default();
+        assert!(cache.get(&block).is_none());
+        assert_eq!(cache.get_or_highlight(&block).len(), 2);
+        assert!(cache.get(&block).is_some());
+    }
+
+    #[test]
+    fn test_expand_tabs_and_dedent() {
+        assert_eq!(expand_tabs("\tx\na\tb", 4), "    x\na   b");
+        assert_eq!(expand_tabs("\tx", 2), "  x");
+        assert_eq!(
+            dedent("        if x {\n\n            y();\n        }"),
+            "if x {\n\n    y();\n}"
+        );
+        assert_eq!(dedent("a\n  b"), "a\n  b");
+    }
+
+    #[test]
+    fn test_detect_language() {
+        let detect = |code: &str, file: Option<&str>| detect_language(code, file);
+        assert_eq!(
+            detect("#!/usr/bin/env python3\nprint(1)", None).as_deref(),
+            Some("py")
+        );
+        assert_eq!(
+            detect("#!/bin/bash\nls", Some("x.rs")).as_deref(),
+            Some("sh")
+        );
+        assert_eq!(detect("x = 1", Some("src/app.rb")).as_deref(), Some("rb"));
+        assert_eq!(detect("\nuse std::io;", None).as_deref(), Some("rs"));
+        assert_eq!(detect("package main", None).as_deref(), Some("go"));
+        assert_eq!(detect("hello world", None), None);
+    }
+
+    #[test]
+    fn test_load_syntaxes() {
+        let dir = std::env::temp_dir().join(format!("tui-slides-syntaxes-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let syntax = "%YAML 1.2\n---\nname: Kdl\nfile_extensions: [kdl]\nscope: source.kdl\n\
+                      contexts:\n  main:\n    - match: '\\bnode\\b'\n      scope: keyword.kdl\n";
+        std::fs::write(dir.join("kdl.sublime-syntax"), syntax).unwrap();
+        let dirs = vec![dir.clone()];
+        let syntaxes = add_syntax_dirs(SyntaxSetBuilder::new(), &dirs)
+            .unwrap()
+            .build();
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(syntaxes.find_syntax_by_token("kdl").is_some());
+        assert!(add_syntax_dirs(SyntaxSetBuilder::new(), &dirs).is_err());
+    }
+
+    #[test]
+    fn test_emphasize_lines() {
+        let lines = (0..3).map(|i| Line::from(format!("line {}", i))).collect();
+        let modifiers: Vec<Modifier> = emphasize_lines(lines, 10, &[11])
+            .iter()
+            .map(|l| l.spans[0].style.add_modifier)
+            .collect();
+        assert_eq!(
+            modifiers,
+            vec![Modifier::DIM, Modifier::BOLD, Modifier::DIM]
+        );
+    }
+
+    #[test]
+    fn test_step_line_counts() {
+        assert_eq!(step_line_counts(&[], 1, 3), vec![1, 2, 3]);
+        assert_eq!(step_line_counts(&[12, 14], 10, 8), vec![3, 5, 8]);
+        assert_eq!(step_line_counts(&[3, 20], 1, 8), vec![3, 8]);
+        assert_eq!(step_line_counts(&[], 1, 1), vec![1]);
+    }
+
+    #[test]
+    fn test_callouts() {
+        let callouts = vec![
+            CalloutJson {
+                line: 11,
+                text: "lock acquired here".to_string(),
+                extra: Default::default(),
+            },
+            CalloutJson {
+                line: 30,
+                text: "out of range".to_string(),
+                extra: Default::default(),
+            },
+        ];
+        let lines = (0..3).map(|i| Line::from(format!("line {}", i))).collect();
+        let lines = mark_callouts(lines, 10, &callouts);
+        assert_eq!(lines[0].spans.len(), 1);
+        let marked: String = lines[1].spans.iter().map(|s| s.content.as_ref()).collect();
+        assert_eq!(marked, "line 1  1 ");
+        let legend = callout_legend(&callouts, 10, 3);
+        assert_eq!(legend.len(), 1);
+        assert_eq!(legend[0].spans[1].content, " lock acquired here");
+        assert!(callout_legend(&callouts, 10, 1).is_empty());
+    }
+
+    #[test]
+    fn test_type_lines() {
+        let code = "ab\ncd";
+        let lines: Vec<Line<'static>> = code
+            .split('\n')
+            .map(|l| Line::from(l.to_string()))
+            .collect();
+        let text = |lines: Vec<Line<'static>>| -> Vec<String> {
+            lines
+                .iter()
+                .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+                .collect()
+        };
+        assert_eq!(typewriter_units(code, &Typewriter::Chars), 5);
+        assert_eq!(typewriter_units(code, &Typewriter::Lines), 2);
+        assert_eq!(
+            text(type_lines(lines.clone(), &Typewriter::Chars, 1)),
+            vec!["a"]
+        );
+        assert_eq!(
+            text(type_lines(lines.clone(), &Typewriter::Chars, 3)),
+            vec!["ab", ""]
+        );
+        assert_eq!(
+            text(type_lines(lines.clone(), &Typewriter::Chars, 4)),
+            vec!["ab", "c"]
+        );
+        assert_eq!(
+            text(type_lines(lines.clone(), &Typewriter::Chars, 5)),
+            vec!["ab", "cd"]
+        );
+        assert_eq!(text(type_lines(lines, &Typewriter::Lines, 1)), vec!["ab"]);
+    }
+
+    #[test]
+    fn test_inline_code_lines() {
+        let code = Style::default().fg(Color::Yellow);
+        let spans = |text: &str| -> Vec<(String, Style)> {
+            inline_code_lines(text, code)[0]
+                .spans
+                .iter()
+                .map(|s| (s.content.to_string(), s.style))
+                .collect()
+        };
+        assert_eq!(
+            spans("call `run()` now"),
+            vec![
+                ("call ".to_string(), Style::default()),
+                ("run()".to_string(), code),
+                (" now".to_string(), Style::default()),
+            ]
+        );
+        assert_eq!(
+            spans("a `b` c `d"),
+            vec![
+                ("a ".to_string(), Style::default()),
+                ("b".to_string(), code),
+                (" c ".to_string(), Style::default()),
+                ("`d".to_string(), Style::default()),
+            ]
+        );
+        assert_eq!(inline_code_lines("x\n`y`", code).len(), 2);
+    }
+
+    #[test]
+    fn test_diff_lines() {
+        let color = |diff: &str| -> Vec<Option<Color>> {
+            diff_lines(diff).iter().map(|l| l.style.fg).collect()
+        };
+        assert_eq!(
+            color("-old\n+new\n same"),
+            vec![Some(Color::Red), Some(Color::Green), None]
+        );
+        let unified = "--- a/x.rs\n+++ b/x.rs\n@@ -1 +1 @@\n-a\n+b";
+        let lines = diff_lines(unified);
+        assert_eq!(lines[0].style.add_modifier, Modifier::BOLD);
+        assert_eq!(lines[1].style.fg, None);
+        assert_eq!(
+            color(unified)[2..],
+            [Some(Color::Cyan), Some(Color::Red), Some(Color::Green)]
+        );
+    }
+
+    #[test]
+    fn test_ansi_lines() {
+        let lines = ansi_lines(
+            "test ok ... \u{1b}[32mok\u{1b}[0m\r\n\u{1b}[1;38;5;196merror\u{1b}[22m: \u{1b}[Kx",
+        );
+        assert_eq!(lines.len(), 2);
+        assert_eq!(lines[0].spans[0].content, "test ok ... ");
+        assert_eq!(lines[0].spans[1].style.fg, Some(Color::Indexed(2)));
+        assert_eq!(lines[0].spans.len(), 2);
+        let error = &lines[1].spans[0].style;
+        assert_eq!(error.fg, Some(Color::Indexed(196)));
+        assert_eq!(error.add_modifier, Modifier::BOLD);
+        let rest: String = lines[1].spans[1..]
+            .iter()
+            .map(|s| s.content.clone())
+            .collect();
+        assert_eq!(rest, ": x");
+        assert_eq!(lines[1].spans[1].style.add_modifier, Modifier::empty());
+        assert_eq!(lines[1].spans[1].style.fg, Some(Color::Indexed(196)));
+        let link = ansi_lines("\u{1b}]8;;https://x.dev\u{7}x\u{1b}]8;;\u{1b}\\");
+        assert_eq!(link[0].spans[0].content, "x");
+        assert_eq!(link[0].spans.len(), 1);
+    }
+
+    #[test]
+    fn test_code_size() {
+        let code = "fn main() {\n    println!(\"hello\");\n}";
+        assert_eq!(gutter_width(9, 2), 5);
+        assert_eq!(code_width(code, 0), 22);
+        assert_eq!(code_width(code, 5), 27);
+        assert_eq!(code_rows(code, 0, 10, false), 3);
+        assert_eq!(code_rows(code, 0, 10, true), 6);
+        assert_eq!(code_rows("", 0, 10, true), 1);
+    }
+
+    #[test]
+    fn test_number_lines() {
+        let lines = (0..10).map(|i| Line::from(format!("line {}", i))).collect();
+        let numbered: Vec<String> = number_lines(lines, 95)
+            .iter()
+            .map(|l| l.spans.iter().map(|s| s.content.as_ref()).collect())
+            .collect();
+        assert_eq!(numbered[0], " 95 │ line 0");
+        assert_eq!(numbered[9], "104 │ line 9");
+    }
+}
@@ -0,0 +1,59 @@
+/// Slides left by jumps (go to number, search, overview, ...), so the presenter can
+/// go back where they were. Stepping with Next and Previous is not recorded.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct History {
+    back: Vec<usize>,
+    forward: Vec<usize>,
+}
+
+impl History {
+    /// `from` is left by a jump, going forward is no longer possible.
+    pub fn visit(&mut self, from: usize) {
+        if self.back.last() != Some(&from) {
+            self.back.push(from);
+        }
+        self.forward.clear();
+    }
+
+    /// Slide to go back to from `current`.
+    pub fn back(&mut self, current: usize) -> Option<usize> {
+        let index = self.back.pop()?;
+        self.forward.push(current);
+        Some(index)
+    }
+
+    /// Slide left by going back from it.
+    pub fn forward(&mut self, current: usize) -> Option<usize> {
+        let index = self.forward.pop()?;
+        self.back.push(current);
+        Some(index)
+    }
+
+    /// Drop slides which are not in the deck any more.
+    pub fn retain(&mut self, count: usize) {
+        self.back.retain(|&i| i < count);
+        self.forward.retain(|&i| i < count);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_history() {
+        let mut history = History::default();
+        assert_eq!(history.back(3), None);
+        history.visit(3);
+        history.visit(40);
+        assert_eq!(history.back(12), Some(40));
+        assert_eq!(history.back(40), Some(3));
+        assert_eq!(history.forward(3), Some(40));
+        history.visit(40);
+        assert_eq!(history.forward(7), None);
+        history.retain(10);
+        assert_eq!(history.back(7), Some(3));
+    }
+}
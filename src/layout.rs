@@ -5,6 +5,8 @@ const MIN_CONTENT_HEIGHT: u16 = 20;
 
 pub const CONTENT_WIDTH: u16 = 50;
 pub const CONTENT_HEIGHT: u16 = 30;
+pub const TOC_WIDTH: u16 = 30;
+pub const NOTES_WIDTH: u16 = 40;
 
 const VERTICAL_CONSTRAINS: [Constraint; 2] = [
     Constraint::Length(TITLE_HEIGHT),
@@ -49,6 +51,22 @@ pub fn get_title_layout(area: Rect) -> Rect {
     layout[0]
 }
 
+/// Outline pane left of the content box, over the left edge of the slides area when
+/// there is no room next to the box.
+pub fn get_toc_layout(layout: &SlidesLayout) -> Rect {
+    let width = TOC_WIDTH.min(layout.slides.width);
+    let x = layout.content.x.saturating_sub(width + 1).max(layout.slides.x);
+    Rect::new(x, layout.content.y, width, layout.content.height).intersection(layout.slides)
+}
+
+/// Speaker notes pane right of the content box, over the right edge of the slides area
+/// when there is no room next to the box.
+pub fn get_notes_layout(layout: &SlidesLayout) -> Rect {
+    let width = NOTES_WIDTH.min(layout.slides.width);
+    let x = (layout.content.right() + 1).min(layout.slides.right().saturating_sub(width));
+    Rect::new(x, layout.content.y, width, layout.content.height).intersection(layout.slides)
+}
+
 pub fn get_slides_layout(area: Rect, box_width: u16, box_height: u16) -> SlidesLayout {
     let layout = Layout::vertical(VERTICAL_CONSTRAINS).split(area);
     // let center_rect = get_centered_rect_percent(CONTENT_PERCENT_WIDTH, CONTENT_PERCENT_HEIGHT, layout[1]);
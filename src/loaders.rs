@@ -0,0 +1,535 @@
+use std::path::Path;
+
+use ratatui::layout::Rect;
+
+use crate::{
+    config::parse_key_sequence,
+    enums::{BoxSizeJson, ContentJson, SlideContentType, SlideJson, SlidesJson},
+    error::{io, Error, Result},
+};
+
+pub mod asciidoc;
+pub mod org;
+
+/// Default box size for decks parsed from text formats which have no `box_size`.
+pub const DEFAULT_BOX_WIDTH: u16 = 90;
+pub const DEFAULT_BOX_HEIGHT: u16 = 35;
+
+const FLOW_PADDING_X: u16 = 3;
+const FLOW_START_Y: u16 = 7;
+const FLOW_GAP: u16 = 1;
+const FLOW_IMAGE_HEIGHT: u16 = 14;
+
+/// Directory of the slides file, images and other referenced files are relative to it.
+pub fn deck_dir(json_slides: &str) -> &Path {
+    Path::new(json_slides)
+        .parent()
+        .filter(|dir| !dir.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."))
+}
+
+/// Read and parse slides file of any supported format and prepare it for showing.
+pub fn load_slides(path: &str) -> Result<SlidesJson> {
+    present_slides(&read_slides(path)?, path)
+}
+
+/// Read and parse slides file of any supported format, the deck as written.
+pub fn read_slides(path: &str) -> Result<SlidesJson> {
+    let content = std::fs::read_to_string(path)
+        .map_err(io(format!("failed to open slides file '{}'", path)))?;
+    parse_slides(path, &content)
+}
+
+/// Deck as shown: sub-slides put after their section, code of `file` items read and
+/// long items paginated. `source` itself is kept for saving edits.
+pub fn present_slides(source: &SlidesJson, path: &str) -> Result<SlidesJson> {
+    let mut slides = source.clone();
+    slides.slides = flatten_sections(slides.slides);
+    for slide in slides.slides.iter_mut() {
+        resolve_code_files(&mut slide.content, path)?;
+        for key in slide.keys.iter().flat_map(|keys| keys.keys()) {
+            parse_key_sequence(key)
+                .map_err(|e| Error::Parse(format!("invalid slide key '{}': {}", key, e)))?;
+        }
+    }
+    slides.slides = slides.slides.into_iter().flat_map(paginate).collect();
+    Ok(slides)
+}
+
+/// Parse 1-based inclusive line range `start..end`, either side can be left out.
+pub fn parse_line_range(range: &str) -> Option<(usize, Option<usize>)> {
+    let (start, end) = range.trim().split_once("..")?;
+    let end = end.strip_prefix('=').unwrap_or(end).trim();
+    let start = match start.trim() {
+        "" => 1,
+        s => s.parse().ok().filter(|s| *s > 0)?,
+    };
+    let end = match end {
+        "" => None,
+        e => Some(e.parse().ok().filter(|e| *e >= start)?),
+    };
+    Some((start, end))
+}
+
+/// Lines between `slide:begin name` and matching `slide:end` marker lines (comment
+/// style does not matter) with 1-based number of the first one. Markers of other
+/// regions inside are dropped.
+pub fn find_region<'a>(source: &'a str, name: &str) -> Option<(usize, Vec<&'a str>)> {
+    let marker = |line: &str, kind: &str| -> Option<String> {
+        let (_, rest) = line.split_once(&format!("slide:{}", kind))?;
+        Some(
+            rest.trim()
+                .trim_end_matches("*/")
+                .trim_end_matches("-->")
+                .trim()
+                .to_string(),
+        )
+    };
+    let lines: Vec<&str> = source.lines().collect();
+    let begin = lines
+        .iter()
+        .position(|l| marker(l, "begin").as_deref() == Some(name))?;
+    let mut depth = 0;
+    let mut region = vec![];
+    for line in &lines[begin + 1..] {
+        if marker(line, "begin").is_some() {
+            depth += 1;
+            continue;
+        }
+        if let Some(end) = marker(line, "end") {
+            if depth == 0 || end == name {
+                return Some((begin + 2, region));
+            }
+            depth -= 1;
+            continue;
+        }
+        region.push(*line);
+    }
+    None
+}
+
+/// Code of item with `file` read fresh on every load so snippets stay in sync with
+/// the source tree, `start_line` follows the selected range.
+fn resolve_code_files(content: &mut [ContentJson], json_slides: &str) -> Result<()> {
+    for item in content.iter_mut() {
+        for tab in item.tabs.iter_mut().flatten() {
+            resolve_code_files(&mut tab.content, json_slides)?;
+        }
+        let Some(file) = &item.file else {
+            continue;
+        };
+        let source = std::fs::read_to_string(deck_dir(json_slides).join(file))
+            .map_err(io(format!("failed to read code file '{}'", file)))?;
+        let (first_line, source_lines) = match &item.region {
+            Some(region) => find_region(&source, region).ok_or_else(|| {
+                Error::Parse(format!("region '{}' not found in '{}'", region, file))
+            })?,
+            None => (1, source.lines().collect()),
+        };
+        let (start, end) = match &item.lines {
+            Some(lines) => parse_line_range(lines)
+                .ok_or_else(|| Error::Parse(format!("invalid line range '{}'", lines)))?,
+            None => (1, None),
+        };
+        let code: Vec<&str> = source_lines
+            .into_iter()
+            .skip(start - 1)
+            .take(end.map(|end| end + 1 - start).unwrap_or(usize::MAX))
+            .collect();
+        item.content = Some(code.join("\n"));
+        item.start_line.get_or_insert(first_line + start - 1);
+    }
+    Ok(())
+}
+
+/// Sub-slides of `children` put right after their section slide, so navigation stays
+/// linear, every slide of deck with sub-slides gets its section number.
+fn flatten_sections(slides: Vec<SlideJson>) -> Vec<SlideJson> {
+    let has_children = slides
+        .iter()
+        .any(|s| s.children.as_ref().is_some_and(|c| !c.is_empty()));
+    let mut flat = vec![];
+    for (index, mut slide) in slides.into_iter().enumerate() {
+        let children = slide.children.take().unwrap_or_default();
+        slide.origin = (index, 0);
+        slide.section = has_children.then_some((index + 1, 0));
+        flat.push(slide);
+        for (sub, mut child) in children.into_iter().enumerate() {
+            child.children = None;
+            child.origin = (index, sub + 1);
+            child.section = Some((index + 1, sub + 1));
+            flat.push(child);
+        }
+    }
+    flat
+}
+
+/// Slide with `paginate` item too long for its `rect` split into pages titled like
+/// `Log output 1/3`, every page has the same other items. Only the first such item
+/// of slide is paginated.
+fn paginate(slide: SlideJson) -> Vec<SlideJson> {
+    let Some(index) = slide.content.iter().position(|i| i.paginate == Some(true)) else {
+        return vec![slide];
+    };
+    let item = &slide.content[index];
+    let height = item.rect.map(|r| r.height as usize).unwrap_or(0);
+    let pages: Vec<ContentJson> = match (&item.type_, &item.rows) {
+        (SlideContentType::Table, Some(rows)) => {
+            let per_page = height.saturating_sub(item.header.is_some() as usize).max(1);
+            rows.chunks(per_page)
+                .map(|rows| ContentJson {
+                    rows: Some(rows.to_vec()),
+                    ..item.clone()
+                })
+                .collect()
+        }
+        (SlideContentType::CodeHighlight | SlideContentType::Paragraph, _) => {
+            let content = item.content.clone().unwrap_or_default();
+            let lines: Vec<&str> = content.split('\n').collect();
+            let start_line = item.start_line.unwrap_or(1);
+            lines
+                .chunks(height.max(1))
+                .enumerate()
+                .map(|(page, lines)| ContentJson {
+                    content: Some(lines.join("\n")),
+                    // -- numbers continue on next page
+                    start_line: (item.start_line.is_some() || item.line_numbers == Some(true))
+                        .then_some(start_line + page * height.max(1)),
+                    ..item.clone()
+                })
+                .collect()
+        }
+        _ => return vec![slide],
+    };
+    let count = pages.len();
+    if count < 2 {
+        return vec![slide];
+    }
+    pages
+        .into_iter()
+        .enumerate()
+        .map(|(page, item)| {
+            let mut slide = slide.clone();
+            slide.content[index] = item;
+            let title = slide.title.as_deref().unwrap_or_default();
+            slide.title = Some(
+                format!("{} {}/{}", title, page + 1, count)
+                    .trim()
+                    .to_string(),
+            );
+            slide
+        })
+        .collect()
+}
+
+/// Parse slides file content based on the file extension, JSON is the default.
+pub fn parse_slides(path: &str, content: &str) -> Result<SlidesJson> {
+    let extension = Path::new(path)
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    match extension.as_deref() {
+        Some("org") => Ok(org::parse(content)),
+        Some("adoc") | Some("asciidoc") => Ok(asciidoc::parse(content)),
+        _ => serde_json::from_str(content).map_err(|e| Error::Parse(format!("'{}': {}", path, e))),
+    }
+}
+
+/// Parse `WIDTHxHEIGHT` box size value used by text formats.
+pub fn parse_box_size(value: &str) -> Option<BoxSizeJson> {
+    let (w, h) = value.trim().split_once('x')?;
+    Some(BoxSizeJson::new(
+        w.trim().parse().ok()?,
+        h.trim().parse().ok()?,
+    ))
+}
+
+/// Word wrap text into lines no longer than `width` characters.
+pub fn wrap_text(text: &str, width: usize) -> Vec<String> {
+    let mut lines = vec![];
+    for source_line in text.split('\n') {
+        let mut line = String::new();
+        for word in source_line.split_whitespace() {
+            let line_len = line.chars().count();
+            if line_len > 0 && line_len + 1 + word.chars().count() > width {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+/// Stacks slide items from top to bottom inside the content box, so text formats
+/// do not need to specify any `rect`.
+pub struct SlideFlow {
+    width: u16,
+    height: u16,
+    y: u16,
+    content: Vec<ContentJson>,
+}
+
+impl SlideFlow {
+    pub fn new(box_size: &BoxSizeJson) -> Self {
+        Self {
+            width: box_size.width,
+            height: box_size.height,
+            y: FLOW_START_Y,
+            content: vec![],
+        }
+    }
+
+    /// Width available for slide items.
+    pub fn inner_width(&self) -> u16 {
+        self.width.saturating_sub(FLOW_PADDING_X * 2)
+    }
+
+    /// Place the item below the previous one, items out of the box are dropped.
+    pub fn push(&mut self, mut item: ContentJson, height: u16) {
+        let bottom = self.height.saturating_sub(1);
+        if self.y >= bottom || height == 0 {
+            return;
+        }
+        let height = height.min(bottom - self.y);
+        item.rect = Some(Rect::new(
+            FLOW_PADDING_X,
+            self.y,
+            self.inner_width(),
+            height,
+        ));
+        self.y += height + FLOW_GAP;
+        self.content.push(item);
+    }
+
+    pub fn push_paragraph(&mut self, text: &str, color: Option<&str>) {
+        let lines = wrap_text(text, self.inner_width() as usize);
+        let height = lines.len() as u16;
+        self.push(
+            ContentJson {
+                type_: SlideContentType::Paragraph,
+                content: Some(lines.join("\n")),
+                color: Some(color.unwrap_or("#FFFFEE").to_string()),
+                ..Default::default()
+            },
+            height,
+        );
+    }
+
+    pub fn push_line(&mut self, text: &str, color: &str) {
+        self.push(
+            ContentJson {
+                type_: SlideContentType::Line,
+                content: Some(text.to_string()),
+                color: Some(color.to_string()),
+                ..Default::default()
+            },
+            1,
+        );
+    }
+
+    pub fn push_code(&mut self, code: &str, language: Option<String>) {
+        let height = code.lines().count() as u16;
+        self.push(
+            ContentJson {
+                type_: SlideContentType::CodeHighlight,
+                content: Some(code.to_string()),
+                language,
+                ..Default::default()
+            },
+            height,
+        );
+    }
+
+    pub fn push_image(&mut self, path: &str) {
+        self.push(
+            ContentJson {
+                type_: SlideContentType::Image,
+                content: Some(path.to_string()),
+                ..Default::default()
+            },
+            FLOW_IMAGE_HEIGHT,
+        );
+    }
+
+    pub fn push_table(&mut self, header: Option<Vec<String>>, rows: Vec<Vec<String>>) {
+        let height = rows.len() as u16 + header.is_some() as u16;
+        self.push(
+            ContentJson {
+                type_: SlideContentType::Table,
+                header,
+                rows: Some(rows),
+                ..Default::default()
+            },
+            height,
+        );
+    }
+
+    /// Bordered box with the `label` in its border and wrapped text inside.
+    pub fn push_callout(&mut self, label: &str, text: &str, color: &str) {
+        let lines = wrap_text(text, self.inner_width().saturating_sub(4) as usize);
+        let height = lines.len() as u16 + 2;
+        let y = self.y;
+        self.push(
+            ContentJson {
+                type_: SlideContentType::Block,
+                content: Some(label.to_string()),
+                color: Some(color.to_string()),
+                ..Default::default()
+            },
+            height,
+        );
+        if let Some(block) = self
+            .content
+            .last()
+            .and_then(|b| b.rect)
+            .filter(|_| y != self.y)
+        {
+            self.content.push(ContentJson {
+                type_: SlideContentType::Paragraph,
+                content: Some(lines.join("\n")),
+                color: Some("#FFFFEE".to_string()),
+                rect: Some(Rect::new(
+                    block.x + 2,
+                    block.y + 1,
+                    block.width.saturating_sub(4),
+                    block.height.saturating_sub(2),
+                )),
+                ..Default::default()
+            });
+        }
+    }
+
+    pub fn finish(self, title: Option<String>) -> SlideJson {
+        SlideJson::new(title, self.content)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_line_range() {
+        assert_eq!(parse_line_range("10..42"), Some((10, Some(42))));
+        assert_eq!(parse_line_range("10..=42"), Some((10, Some(42))));
+        assert_eq!(parse_line_range("10.."), Some((10, None)));
+        assert_eq!(parse_line_range("..5"), Some((1, Some(5))));
+        assert_eq!(parse_line_range("0..5"), None);
+        assert_eq!(parse_line_range("9..5"), None);
+        assert_eq!(parse_line_range("12"), None);
+    }
+
+    #[test]
+    fn test_find_region() {
+        let source = concat!(
+            "use x;\n// slide:begin main\nfn main() {\n    // slide:begin inner\n",
+            "    run();\n    // slide:end inner\n}\n// slide:end\n",
+            "/* slide:begin css */\nlet y;\n/* slide:end */"
+        );
+        assert_eq!(
+            find_region(source, "main"),
+            Some((3, vec!["fn main() {", "    run();", "}"]))
+        );
+        assert_eq!(find_region(source, "inner"), Some((5, vec!["    run();"])));
+        assert_eq!(find_region(source, "css"), Some((10, vec!["let y;"])));
+        assert_eq!(find_region(source, "missing"), None);
+    }
+
+    #[test]
+    fn test_flatten_sections() {
+        let mut intro = SlideJson::new(Some("Intro".to_string()), vec![]);
+        intro.children = Some(vec![
+            SlideJson::new(Some("Why".to_string()), vec![]),
+            SlideJson::new(Some("How".to_string()), vec![]),
+        ]);
+        let end = SlideJson::new(Some("End".to_string()), vec![]);
+        let slides = flatten_sections(vec![intro, end.clone()]);
+        let sections: Vec<_> = slides
+            .iter()
+            .map(|s| (s.title.clone(), s.section))
+            .collect();
+        assert_eq!(
+            sections,
+            vec![
+                (Some("Intro".to_string()), Some((1, 0))),
+                (Some("Why".to_string()), Some((1, 1))),
+                (Some("How".to_string()), Some((1, 2))),
+                (Some("End".to_string()), Some((2, 0))),
+            ]
+        );
+        assert!(slides[0].children.is_none());
+        assert_eq!(flatten_sections(vec![end.clone()]), vec![end]);
+    }
+
+    #[test]
+    fn test_paginate() {
+        let item = ContentJson {
+            type_: SlideContentType::CodeHighlight,
+            content: Some(String::from("1\n2\n3\n4\n5")),
+            rect: Some(Rect::new(0, 0, 10, 2)),
+            start_line: Some(10),
+            paginate: Some(true),
+            ..Default::default()
+        };
+        let slide = SlideJson::new(Some(String::from("Log output")), vec![item.clone()]);
+        let pages = paginate(slide.clone());
+        let titles: Vec<_> = pages.iter().map(|s| s.title.clone().unwrap()).collect();
+        assert_eq!(
+            titles,
+            vec!["Log output 1/3", "Log output 2/3", "Log output 3/3"]
+        );
+        assert_eq!(pages[1].content[0].content.as_deref(), Some("3\n4"));
+        assert_eq!(pages[2].content[0].start_line, Some(14));
+
+        let table = ContentJson {
+            type_: SlideContentType::Table,
+            rect: Some(Rect::new(0, 0, 10, 3)),
+            header: Some(vec![String::from("n")]),
+            rows: Some((0..4).map(|i| vec![i.to_string()]).collect()),
+            paginate: Some(true),
+            ..Default::default()
+        };
+        let pages = paginate(SlideJson::new(None, vec![table]));
+        assert_eq!(pages.len(), 2);
+        assert_eq!(pages[0].title.as_deref(), Some("1/2"));
+        assert_eq!(pages[1].content[0].rows.as_ref().unwrap().len(), 2);
+
+        let fits = SlideJson::new(
+            None,
+            vec![ContentJson {
+                rect: Some(Rect::new(0, 0, 10, 5)),
+                ..item
+            }],
+        );
+        assert_eq!(paginate(fits.clone()), vec![fits]);
+    }
+
+    #[test]
+    fn test_present_slides_keeps_source() {
+        let item = ContentJson {
+            type_: SlideContentType::Paragraph,
+            content: Some(String::from("1\n2\n3")),
+            rect: Some(Rect::new(0, 0, 10, 1)),
+            paginate: Some(true),
+            ..Default::default()
+        };
+        let source = SlidesJson::new(
+            BoxSizeJson::new(90, 35),
+            vec![
+                SlideJson::new(Some(String::from("Intro")), vec![]),
+                SlideJson::new(Some(String::from("Log")), vec![item]),
+            ],
+        );
+        let slides = present_slides(&source, "slides.json").unwrap();
+        let origins: Vec<_> = slides.slides.iter().map(|s| s.origin).collect();
+        assert_eq!(origins, vec![(0, 0), (1, 0), (1, 0), (1, 0)]);
+        assert_eq!(slides.slides[1].title.as_deref(), Some("Log 1/3"));
+        assert_eq!(source.slides[1].title.as_deref(), Some("Log"));
+    }
+}
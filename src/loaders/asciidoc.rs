@@ -0,0 +1,238 @@
+use crate::{
+    enums::{BoxSizeJson, SlidesJson},
+    loaders::{parse_box_size, SlideFlow, DEFAULT_BOX_HEIGHT, DEFAULT_BOX_WIDTH},
+};
+
+const SUBHEADING_COLOR: &str = "#FFFF00";
+const ADMONITIONS: [(&str, &str); 5] = [
+    ("NOTE", "#5599FF"),
+    ("TIP", "#55FF55"),
+    ("IMPORTANT", "#FF55FF"),
+    ("CAUTION", "#FFAA00"),
+    ("WARNING", "#FF5555"),
+];
+
+struct AdocSlide {
+    title: String,
+    lines: Vec<String>,
+}
+
+/// Parse AsciiDoc document, every level 1 section (`== Title`) starts a new slide.
+pub fn parse(content: &str) -> SlidesJson {
+    let mut box_size = BoxSizeJson::new(DEFAULT_BOX_WIDTH, DEFAULT_BOX_HEIGHT);
+    let mut adoc_slides: Vec<AdocSlide> = vec![];
+
+    for line in content.lines() {
+        if let Some(title) = line.strip_prefix("== ") {
+            adoc_slides.push(AdocSlide {
+                title: title.trim().to_string(),
+                lines: vec![],
+            });
+        } else if let Some(slide) = adoc_slides.last_mut() {
+            slide.lines.push(line.to_string());
+        } else if let Some(value) = line.trim().strip_prefix(":box-size:") {
+            if let Some(size) = parse_box_size(value) {
+                box_size = size;
+            }
+        }
+    }
+
+    let slides = adoc_slides
+        .into_iter()
+        .map(|s| {
+            let mut flow = SlideFlow::new(&box_size);
+            parse_body(&s.lines, &mut flow);
+            flow.finish(Some(s.title))
+        })
+        .collect();
+
+    SlidesJson::new(box_size, slides)
+}
+
+fn admonition_color(label: &str) -> Option<&'static str> {
+    ADMONITIONS
+        .iter()
+        .find(|(name, _)| *name == label)
+        .map(|(_, color)| *color)
+}
+
+/// `NOTE: text` paragraph admonition.
+fn inline_admonition(line: &str) -> Option<(&str, &str)> {
+    let (label, text) = line.split_once(": ")?;
+    admonition_color(label).map(|_| (label, text))
+}
+
+/// Block attribute line like `[source,rust]` or `[NOTE]`.
+fn block_attributes(line: &str) -> Option<Vec<&str>> {
+    let inner = line.strip_prefix('[')?.strip_suffix(']')?;
+    Some(inner.split(',').map(|a| a.trim()).collect())
+}
+
+fn is_delimiter(line: &str) -> bool {
+    let first = line.chars().next();
+    line.len() >= 4
+        && matches!(first, Some('-' | '.' | '=' | '*' | '_'))
+        && line.chars().all(|c| Some(c) == first)
+}
+
+fn image_macro(line: &str) -> Option<&str> {
+    let rest = line.strip_prefix("image::")?;
+    rest.split('[').next()
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    ["* ", "- ", ". "]
+        .iter()
+        .find_map(|marker| line.strip_prefix(marker))
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    line.trim_start_matches('|')
+        .split('|')
+        .map(|c| c.trim().to_string())
+        .collect()
+}
+
+fn parse_body(lines: &[String], flow: &mut SlideFlow) {
+    let mut paragraph: Vec<String> = vec![];
+    let mut attributes: Vec<String> = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let trimmed = lines[i].trim();
+
+        let is_text = !trimmed.is_empty()
+            && !trimmed.starts_with("//")
+            && !trimmed.starts_with('=')
+            && !trimmed.starts_with("|===")
+            && block_attributes(trimmed).is_none()
+            && image_macro(trimmed).is_none()
+            && inline_admonition(trimmed).is_none()
+            && !is_delimiter(trimmed);
+        if is_text {
+            match (list_item(trimmed), paragraph.last_mut()) {
+                (Some(item), _) => paragraph.push(format!("▸ {}", item)),
+                // -- continuation of the previous text line
+                (None, Some(last)) => {
+                    last.push(' ');
+                    last.push_str(trimmed);
+                }
+                (None, None) => paragraph.push(trimmed.to_string()),
+            }
+            i += 1;
+            continue;
+        }
+
+        flush_paragraph(&mut paragraph, flow);
+
+        if let Some(attrs) = block_attributes(trimmed) {
+            attributes = attrs.iter().map(|a| a.to_string()).collect();
+            i += 1;
+            continue;
+        }
+
+        if is_delimiter(trimmed) {
+            let delimiter = trimmed;
+            let mut body = vec![];
+            i += 1;
+            while i < lines.len() && lines[i].trim() != delimiter {
+                body.push(lines[i].as_str());
+                i += 1;
+            }
+            let body = body.join("\n");
+            let first = attributes.first().map(|a| a.as_str());
+            match (delimiter.chars().next(), first) {
+                (Some('-'), _) | (Some('.'), _) => {
+                    let language = match first {
+                        Some("source") => attributes.get(1).cloned(),
+                        _ => None,
+                    };
+                    flow.push_code(&body, language);
+                }
+                (_, Some(label)) if admonition_color(label).is_some() => {
+                    let color = admonition_color(label).unwrap_or_default();
+                    flow.push_callout(label, &body, color);
+                }
+                _ => flow.push_paragraph(&body, None),
+            }
+        } else if trimmed.starts_with("|===") {
+            let mut rows = vec![];
+            let mut header = None;
+            i += 1;
+            while i < lines.len() && !lines[i].trim().starts_with("|===") {
+                let row = lines[i].trim();
+                if row.is_empty() && rows.len() == 1 && header.is_none() {
+                    header = rows.pop();
+                } else if row.starts_with('|') {
+                    rows.push(table_cells(row));
+                }
+                i += 1;
+            }
+            flow.push_table(header, rows);
+        } else if let Some((label, text)) = inline_admonition(trimmed) {
+            let color = admonition_color(label).unwrap_or_default();
+            flow.push_callout(label, text, color);
+        } else if let Some(path) = image_macro(trimmed) {
+            flow.push_image(path);
+        } else if trimmed.starts_with("===") {
+            let heading = trimmed.trim_start_matches('=').trim();
+            flow.push_line(heading, SUBHEADING_COLOR);
+        }
+        attributes.clear();
+        i += 1;
+    }
+
+    flush_paragraph(&mut paragraph, flow);
+}
+
+fn flush_paragraph(paragraph: &mut Vec<String>, flow: &mut SlideFlow) {
+    if !paragraph.is_empty() {
+        flow.push_paragraph(&paragraph.join("\n"), None);
+        paragraph.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::enums::SlideContentType;
+
+    #[test]
+    fn test_sections_to_slides() {
+        let slides = parse("= Talk\n:box-size: 70x25\n\n== First\ntext\n=== Sub\n== Second\n");
+        assert_eq!(slides.box_size.width, 70);
+        assert_eq!(slides.slides.len(), 2);
+        assert_eq!(slides.slides[0].title, Some("First".to_string()));
+        assert_eq!(
+            slides.slides[0].content[0].type_,
+            SlideContentType::Paragraph
+        );
+        assert_eq!(slides.slides[0].content[1].type_, SlideContentType::Line);
+    }
+
+    #[test]
+    fn test_listing_and_image() {
+        let slides =
+            parse("== Code\n[source,rust]\n----\nfn main() {}\n----\nimage::images/a.png[A]\n");
+        let code = &slides.slides[0].content[0];
+        assert_eq!(code.type_, SlideContentType::CodeHighlight);
+        assert_eq!(code.language, Some("rust".to_string()));
+        assert_eq!(code.content, Some("fn main() {}".to_string()));
+        let image = &slides.slides[0].content[1];
+        assert_eq!(image.type_, SlideContentType::Image);
+        assert_eq!(image.content, Some("images/a.png".to_string()));
+    }
+
+    #[test]
+    fn test_admonitions() {
+        let slides = parse("== Tips\nNOTE: inline note\n\n[WARNING]\n====\nblock warning\n====\n");
+        let content = &slides.slides[0].content;
+        assert_eq!(content.len(), 4);
+        assert_eq!(content[0].type_, SlideContentType::Block);
+        assert_eq!(content[0].content, Some("NOTE".to_string()));
+        assert_eq!(content[1].content, Some("inline note".to_string()));
+        assert_eq!(content[2].content, Some("WARNING".to_string()));
+        assert_eq!(content[3].content, Some("block warning".to_string()));
+    }
+}
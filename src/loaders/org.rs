@@ -0,0 +1,245 @@
+use crate::{
+    enums::{BoxSizeJson, SlidesJson},
+    loaders::{parse_box_size, SlideFlow, DEFAULT_BOX_HEIGHT, DEFAULT_BOX_WIDTH},
+};
+
+const SUBHEADING_COLOR: &str = "#FFFF00";
+
+struct OrgSlide {
+    title: String,
+    lines: Vec<String>,
+}
+
+/// Parse org-mode document, every top-level heading (`* Title`) starts a new slide.
+pub fn parse(content: &str) -> SlidesJson {
+    let mut box_size = BoxSizeJson::new(DEFAULT_BOX_WIDTH, DEFAULT_BOX_HEIGHT);
+    let mut org_slides: Vec<OrgSlide> = vec![];
+
+    for line in content.lines() {
+        if let Some(title) = line.strip_prefix("* ") {
+            org_slides.push(OrgSlide {
+                title: title.trim().to_string(),
+                lines: vec![],
+            });
+        } else if let Some(slide) = org_slides.last_mut() {
+            slide.lines.push(line.to_string());
+        } else if let Some(value) = keyword_value(line, "BOX_SIZE") {
+            if let Some(size) = parse_box_size(value) {
+                box_size = size;
+            }
+        }
+    }
+
+    let slides = org_slides
+        .into_iter()
+        .map(|s| {
+            let mut flow = SlideFlow::new(&box_size);
+            parse_body(&s.lines, &mut flow);
+            flow.finish(Some(s.title))
+        })
+        .collect();
+
+    SlidesJson::new(box_size, slides)
+}
+
+/// Value of `#+KEYWORD: value` line, keyword is case insensitive.
+fn keyword_value<'a>(line: &'a str, keyword: &str) -> Option<&'a str> {
+    let rest = line.trim().strip_prefix("#+")?;
+    let (key, value) = rest.split_once(':')?;
+    key.eq_ignore_ascii_case(keyword).then_some(value.trim())
+}
+
+fn block_start(line: &str) -> Option<(String, Option<String>)> {
+    let trimmed = line.trim();
+    if !trimmed
+        .get(..8)
+        .is_some_and(|prefix| prefix.eq_ignore_ascii_case("#+BEGIN_"))
+    {
+        return None;
+    }
+    let mut parts = trimmed[8..].split_whitespace();
+    let name = parts.next()?.to_uppercase();
+    Some((name, parts.next().map(|s| s.to_string())))
+}
+
+fn is_block_end(line: &str, name: &str) -> bool {
+    line.trim().eq_ignore_ascii_case(&format!("#+END_{}", name))
+}
+
+fn table_cells(line: &str) -> Vec<String> {
+    line.trim()
+        .trim_matches('|')
+        .split('|')
+        .map(|c| c.trim().to_string())
+        .collect()
+}
+
+fn image_link(line: &str) -> Option<String> {
+    let link = line.trim().strip_prefix("[[")?.strip_suffix("]]")?;
+    let target = link.split("][").next()?;
+    let target = target.strip_prefix("file:").unwrap_or(target);
+    let lower = target.to_lowercase();
+    [".png", ".jpg", ".jpeg", ".gif", ".bmp", ".webp"]
+        .iter()
+        .any(|ext| lower.ends_with(ext))
+        .then(|| target.to_string())
+}
+
+fn is_drawer(line: &str) -> bool {
+    line.len() > 1 && line.starts_with(':') && line.ends_with(':')
+}
+
+fn list_item(line: &str) -> Option<&str> {
+    let trimmed = line.trim_start();
+    if let Some(item) = trimmed
+        .strip_prefix("- ")
+        .or_else(|| trimmed.strip_prefix("+ "))
+    {
+        return Some(item);
+    }
+    let (number, item) = trimmed.split_once(". ")?;
+    number.chars().all(|c| c.is_ascii_digit()).then_some(item)
+}
+
+fn parse_body(lines: &[String], flow: &mut SlideFlow) {
+    let mut paragraph: Vec<String> = vec![];
+    let mut i = 0;
+    while i < lines.len() {
+        let line = &lines[i];
+        let trimmed = line.trim();
+
+        let is_text = !trimmed.is_empty()
+            && !trimmed.starts_with('|')
+            && !trimmed.starts_with('#')
+            && !trimmed.starts_with("**")
+            && !is_drawer(trimmed)
+            && image_link(trimmed).is_none();
+        if is_text {
+            match (list_item(line), paragraph.last_mut()) {
+                (Some(item), _) => paragraph.push(format!("▸ {}", item)),
+                // -- continuation of the previous text line
+                (None, Some(last)) => {
+                    last.push(' ');
+                    last.push_str(trimmed);
+                }
+                (None, None) => paragraph.push(trimmed.to_string()),
+            }
+            i += 1;
+            continue;
+        }
+
+        flush_paragraph(&mut paragraph, flow);
+
+        if let Some((name, arg)) = block_start(trimmed) {
+            let mut body = vec![];
+            i += 1;
+            while i < lines.len() && !is_block_end(&lines[i], &name) {
+                body.push(lines[i].as_str());
+                i += 1;
+            }
+            if name == "SRC" || name == "EXAMPLE" {
+                flow.push_code(&body.join("\n"), arg);
+            } else {
+                flow.push_paragraph(&body.join("\n"), None);
+            }
+        } else if trimmed.starts_with('|') {
+            let mut header = None;
+            let mut rows = vec![];
+            while i < lines.len() && lines[i].trim().starts_with('|') {
+                let row = lines[i].trim();
+                if row.starts_with("|-") {
+                    if header.is_none() && !rows.is_empty() {
+                        header = rows.pop();
+                    }
+                } else {
+                    rows.push(table_cells(row));
+                }
+                i += 1;
+            }
+            flow.push_table(header, rows);
+            continue;
+        } else if trimmed.starts_with('*') {
+            let heading = trimmed.trim_start_matches('*').trim();
+            flow.push_line(heading, SUBHEADING_COLOR);
+        } else if is_drawer(trimmed) && trimmed != ":END:" {
+            // -- skip drawers like :PROPERTIES:
+            while i < lines.len() && lines[i].trim() != ":END:" {
+                i += 1;
+            }
+        } else if let Some(path) = image_link(trimmed) {
+            flow.push_image(&path);
+        }
+        i += 1;
+    }
+
+    flush_paragraph(&mut paragraph, flow);
+}
+
+fn flush_paragraph(paragraph: &mut Vec<String>, flow: &mut SlideFlow) {
+    if !paragraph.is_empty() {
+        flow.push_paragraph(&paragraph.join("\n"), None);
+        paragraph.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::enums::SlideContentType;
+
+    #[test]
+    fn test_headings_to_slides() {
+        let slides = parse("#+TITLE: talk\n* First\ntext\n** Sub\n* Second\n");
+        assert_eq!(slides.slides.len(), 2);
+        assert_eq!(slides.slides[0].title, Some("First".to_string()));
+        assert_eq!(
+            slides.slides[0].content[0].type_,
+            SlideContentType::Paragraph
+        );
+        assert_eq!(slides.slides[0].content[1].type_, SlideContentType::Line);
+        assert_eq!(slides.slides[1].content.len(), 0);
+    }
+
+    #[test]
+    fn test_non_ascii_lines() {
+        let slides = parse("* Ünïcödé\n- aüüü item\n");
+        assert_eq!(slides.slides[0].title, Some("Ünïcödé".to_string()));
+        // -- byte 8 is inside "ü"
+        assert_eq!(block_start("- aüüü"), None);
+        assert_eq!(
+            block_start("#+begin_src rust"),
+            Some(("SRC".to_string(), Some("rust".to_string())))
+        );
+    }
+
+    #[test]
+    fn test_src_block() {
+        let slides = parse("* Code\n#+begin_src python\nprint(1)\nprint(2)\n#+end_src\n");
+        let code = &slides.slides[0].content[0];
+        assert_eq!(code.type_, SlideContentType::CodeHighlight);
+        assert_eq!(code.language, Some("python".to_string()));
+        assert_eq!(code.content, Some("print(1)\nprint(2)".to_string()));
+        assert_eq!(code.rect.unwrap().height, 2);
+    }
+
+    #[test]
+    fn test_table() {
+        let slides = parse("* Table\n| a | b |\n|---+---|\n| 1 | 2 |\n| 3 | 4 |\n");
+        let table = &slides.slides[0].content[0];
+        assert_eq!(table.type_, SlideContentType::Table);
+        assert_eq!(table.header, Some(vec!["a".to_string(), "b".to_string()]));
+        assert_eq!(table.rows.as_ref().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_box_size_and_image() {
+        let slides = parse("#+BOX_SIZE: 60x20\n* Img\n[[file:./images/a.png]]\n");
+        assert_eq!(slides.box_size.width, 60);
+        assert_eq!(slides.box_size.height, 20);
+        let image = &slides.slides[0].content[0];
+        assert_eq!(image.type_, SlideContentType::Image);
+        assert_eq!(image.content, Some("./images/a.png".to_string()));
+    }
+}
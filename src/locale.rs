@@ -0,0 +1,133 @@
+use std::sync::{Arc, RwLock};
+
+use chrono::NaiveDate;
+
+use crate::error::{Error, Result};
+
+/// Integers with fewer digits are not grouped, so years in tables stay `2024`.
+const MIN_GROUPING_DIGITS: usize = 5;
+
+/// Number and date format of data widgets, set by `locale` of the deck.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Locale {
+    pub thousands: &'static str,
+    pub decimal: &'static str,
+    pub date_format: &'static str,
+}
+
+impl Locale {
+    /// Locale of language tag like `de` or `en-GB`, region is only used when it
+    /// changes the format.
+    pub fn from_tag(tag: &str) -> Result<Self> {
+        let tag = tag.replace('_', "-").to_lowercase();
+        let language = tag.split('-').next().unwrap_or_default();
+        let (thousands, decimal, date_format) = match (tag.as_str(), language) {
+            ("en-us", _) | ("en", _) => (",", ".", "%m/%d/%Y"),
+            ("en-gb" | "en-au" | "en-ie" | "en-nz", _) => (",", ".", "%d/%m/%Y"),
+            ("de-ch", _) => ("'", ".", "%d.%m.%Y"),
+            (_, "en") => (",", ".", "%d/%m/%Y"),
+            (_, "de") => (".", ",", "%d.%m.%Y"),
+            (_, "fr") => ("\u{202f}", ",", "%d/%m/%Y"),
+            (_, "es" | "it" | "pt") => (".", ",", "%d/%m/%Y"),
+            (_, "nl") => (".", ",", "%d-%m-%Y"),
+            (_, "pl" | "cs" | "sk" | "ru" | "uk") => ("\u{a0}", ",", "%d.%m.%Y"),
+            (_, "sv" | "nb" | "fi") => ("\u{a0}", ",", "%Y-%m-%d"),
+            (_, "ja" | "zh" | "ko") => (",", ".", "%Y/%m/%d"),
+            _ => {
+                return Err(Error::Parse(format!(
+                    "unknown locale '{}', use e.g. 'en', 'en-GB', 'de' or 'fr'",
+                    tag
+                )))
+            }
+        };
+        Ok(Self {
+            thousands,
+            decimal,
+            date_format,
+        })
+    }
+
+    /// `value` with `decimals` digits after the decimal mark and grouped thousands.
+    pub fn number(&self, value: f64, decimals: usize) -> String {
+        let text = format!("{:.*}", decimals, value.abs());
+        let (integer, fraction) = text.split_once('.').unwrap_or((&text, ""));
+        let mut grouped = String::new();
+        for (i, c) in integer.chars().enumerate() {
+            if i > 0 && (integer.len() - i) % 3 == 0 {
+                grouped.push_str(self.thousands);
+            }
+            grouped.push(c);
+        }
+        let sign = if value < 0.0 && text.chars().any(|c| c.is_ascii_digit() && c != '0') {
+            "-"
+        } else {
+            ""
+        };
+        if fraction.is_empty() {
+            format!("{}{}", sign, grouped)
+        } else {
+            format!("{}{}{}{}", sign, grouped, self.decimal, fraction)
+        }
+    }
+
+    /// Table cell in locale format when it is a plain number (`-1234.5`) or an ISO date
+    /// (`2024-03-01`), other cells are kept as they are.
+    pub fn cell(&self, cell: &str) -> String {
+        let trimmed = cell.trim();
+        if let Ok(date) = NaiveDate::parse_from_str(trimmed, "%Y-%m-%d") {
+            return date.format(self.date_format).to_string();
+        }
+        let digits = trimmed.strip_prefix('-').unwrap_or(trimmed);
+        let (integer, fraction) = digits.split_once('.').unwrap_or((digits, ""));
+        let plain = !integer.is_empty()
+            && integer.chars().all(|c| c.is_ascii_digit())
+            && fraction.chars().all(|c| c.is_ascii_digit());
+        if !plain {
+            return cell.to_string();
+        }
+        match trimmed.parse::<f64>() {
+            Ok(value) if integer.len() >= MIN_GROUPING_DIGITS => self.number(value, fraction.len()),
+            Ok(_) => trimmed.replacen('.', self.decimal, 1),
+            Err(_) => cell.to_string(),
+        }
+    }
+}
+
+static LOCALE: RwLock<Option<Arc<Locale>>> = RwLock::new(None);
+
+/// Locale of the loaded deck, `None` when the deck has none and widgets keep their
+/// own format.
+pub fn locale() -> Option<Arc<Locale>> {
+    LOCALE.read().ok().and_then(|l| l.clone())
+}
+
+pub fn set_locale(locale: Option<Locale>) {
+    if let Ok(mut current) = LOCALE.write() {
+        *current = locale.map(Arc::new);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_locale_number_and_cell() {
+        let de = Locale::from_tag("de-DE").unwrap();
+        assert_eq!(de.number(1234567.891, 2), "1.234.567,89");
+        assert_eq!(de.number(-999.0, 0), "-999");
+        assert_eq!(de.number(-0.001, 1), "0,0");
+        assert_eq!(de.cell("12345"), "12.345");
+        assert_eq!(de.cell("2024"), "2024");
+        assert_eq!(de.cell("3.5"), "3,5");
+        assert_eq!(de.cell("2024-03-01"), "01.03.2024");
+        assert_eq!(de.cell("v1.2"), "v1.2");
+
+        let us = Locale::from_tag("en").unwrap();
+        assert_eq!(us.number(1000.5, 1), "1,000.5");
+        assert_eq!(us.cell("2024-03-01"), "03/01/2024");
+        assert!(Locale::from_tag("xx").is_err());
+    }
+}
@@ -1,25 +1,51 @@
 #![allow(dead_code)]
 #![allow(unused_imports)]
 #![allow(unused_variables)]
+#![allow(clippy::identity_op)]
 
 pub mod action;
 pub mod app;
+pub mod audio;
+pub mod bookmarks;
+pub mod cache;
+pub mod capabilities;
 pub mod cli;
 pub mod components;
 pub mod config;
+pub mod diagrams;
 pub mod mode;
+pub mod notes;
+pub mod osc;
+pub mod presenter;
+pub mod pty;
+pub mod rehearsal;
 pub mod tui;
 pub mod utils;
 pub mod enums;
+pub mod error;
+pub mod exec;
+pub mod highlight;
+pub mod history;
 pub mod layout;
+pub mod loaders;
+pub mod locale;
+pub mod math;
+pub mod navigation;
+pub mod search;
 pub mod slide_builder;
+pub mod theme;
+pub mod widgets;
+pub mod writer;
 
 use clap::Parser;
-use cli::Cli;
-use color_eyre::eyre::Result;
+use cli::{CacheCommand, Cli, Command};
+use color_eyre::eyre::{bail, Result};
+use syntect::highlighting::ThemeSet;
 
 use crate::{
   app::App,
+  components::slides::Slides,
+  config::Config,
   utils::{initialize_logging, initialize_panic_handler, version},
 };
 
@@ -29,12 +55,87 @@ async fn tokio_main() -> Result<()> {
   initialize_panic_handler()?;
 
   let args = Cli::parse();
-  let mut app = App::new(args.tick_rate, args.frame_rate, args.json_slides)?;
+  if args.light {
+    let detected = capabilities::Capabilities::detect();
+    capabilities::init(capabilities::Capabilities { light: true, ..detected });
+  }
+  if let Some(command) = args.command {
+    return run_command(command, &args.json_slides);
+  }
+  if let Some(theme) = &args.code_theme {
+    let themes = ThemeSet::load_defaults();
+    if !themes.themes.contains_key(theme) {
+      let names: Vec<&str> = themes.themes.keys().map(String::as_str).collect();
+      bail!("Unknown code theme '{}', available themes: {}", theme, names.join(", "));
+    }
+  }
+  let slides = Slides::new()
+    .image_protocol(args.image_protocol)
+    .offline(args.offline)
+    .code_theme(args.code_theme)
+    .autoplay(args.autoplay)
+    .duration(args.duration)
+    .presenter_view(args.presenter)
+    .rehearse(args.rehearse);
+  let mut app = App::new(args.tick_rate, args.frame_rate, args.json_slides, slides)?
+  .no_loop(args.no_loop)
+  .kiosk(args.kiosk);
   app.run().await?;
 
   Ok(())
 }
 
+fn run_command(command: Command, json_slides: &str) -> Result<()> {
+  match command {
+    Command::Export { json, output } => {
+      if !json {
+        bail!("only JSON export is supported, use `export --json`");
+      }
+      let slides = loaders::load_slides(json_slides)?;
+      match output {
+        Some(path) => writer::write_slides(&path, &slides)?,
+        None => print!("{}", writer::slides_to_string(&slides)?),
+      }
+    },
+    Command::Warm { force } | Command::Cache { command: CacheCommand::Warm { force } } => {
+      warm(json_slides, force)?;
+    },
+    Command::Cache { command: CacheCommand::Status } => {
+      let slides = loaders::load_slides(json_slides)?;
+      let entries = cache::cache_entries(&slides, json_slides)?;
+      let (used, stale): (Vec<_>, Vec<_>) = entries.iter().partition(|e| e.used);
+      let size = |entries: &[&cache::CacheEntry]| entries.iter().map(|e| e.size).sum::<u64>() / 1024;
+      println!("{}", diagrams::cache_dir(json_slides).display());
+      println!("{} files, {} KiB used by the deck", used.len(), size(&used));
+      println!("{} files, {} KiB no longer used", stale.len(), size(&stale));
+    },
+    Command::Cache { command: CacheCommand::Clear { stale } } => {
+      let slides = loaders::load_slides(json_slides)?;
+      let removed = cache::clear(&slides, json_slides, stale)?;
+      println!("{} files removed", removed);
+    },
+  }
+  Ok(())
+}
+
+fn warm(json_slides: &str, force: bool) -> Result<()> {
+  let slides = loaders::load_slides(json_slides)?;
+  let report = diagrams::warm_cache(&slides, json_slides, force);
+  for e in &report.failed {
+    eprintln!("failed: {}", e);
+  }
+  println!("{} rendered, {} already cached, {} failed", report.rendered, report.cached, report.failed.len());
+  let max_bytes = Config::new()?.cache.max_size_mb * 1024 * 1024;
+  let removed = cache::prune(&slides, json_slides, max_bytes)?;
+  if removed > 0 {
+    println!("{} unused files removed to keep cache under {} MB", removed, max_bytes / 1024 / 1024);
+  }
+  if !report.failed.is_empty() {
+    bail!("some content could not be prefetched");
+  }
+  Ok(())
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
   if let Err(e) = tokio_main().await {
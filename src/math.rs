@@ -0,0 +1,310 @@
+use std::{iter::Peekable, str::Chars};
+
+const SUPERSCRIPTS: &[(char, char)] = &[
+    ('0', '⁰'),
+    ('1', '¹'),
+    ('2', '²'),
+    ('3', '³'),
+    ('4', '⁴'),
+    ('5', '⁵'),
+    ('6', '⁶'),
+    ('7', '⁷'),
+    ('8', '⁸'),
+    ('9', '⁹'),
+    ('+', '⁺'),
+    ('-', '⁻'),
+    ('=', '⁼'),
+    ('(', '⁽'),
+    (')', '⁾'),
+    ('a', 'ᵃ'),
+    ('b', 'ᵇ'),
+    ('c', 'ᶜ'),
+    ('d', 'ᵈ'),
+    ('e', 'ᵉ'),
+    ('f', 'ᶠ'),
+    ('g', 'ᵍ'),
+    ('h', 'ʰ'),
+    ('i', 'ⁱ'),
+    ('j', 'ʲ'),
+    ('k', 'ᵏ'),
+    ('l', 'ˡ'),
+    ('m', 'ᵐ'),
+    ('n', 'ⁿ'),
+    ('o', 'ᵒ'),
+    ('p', 'ᵖ'),
+    ('r', 'ʳ'),
+    ('s', 'ˢ'),
+    ('t', 'ᵗ'),
+    ('u', 'ᵘ'),
+    ('v', 'ᵛ'),
+    ('w', 'ʷ'),
+    ('x', 'ˣ'),
+    ('y', 'ʸ'),
+    ('z', 'ᶻ'),
+    ('T', 'ᵀ'),
+];
+
+const SUBSCRIPTS: &[(char, char)] = &[
+    ('0', '₀'),
+    ('1', '₁'),
+    ('2', '₂'),
+    ('3', '₃'),
+    ('4', '₄'),
+    ('5', '₅'),
+    ('6', '₆'),
+    ('7', '₇'),
+    ('8', '₈'),
+    ('9', '₉'),
+    ('+', '₊'),
+    ('-', '₋'),
+    ('=', '₌'),
+    ('(', '₍'),
+    (')', '₎'),
+    ('a', 'ₐ'),
+    ('e', 'ₑ'),
+    ('h', 'ₕ'),
+    ('i', 'ᵢ'),
+    ('j', 'ⱼ'),
+    ('k', 'ₖ'),
+    ('l', 'ₗ'),
+    ('m', 'ₘ'),
+    ('n', 'ₙ'),
+    ('o', 'ₒ'),
+    ('p', 'ₚ'),
+    ('r', 'ᵣ'),
+    ('s', 'ₛ'),
+    ('t', 'ₜ'),
+    ('u', 'ᵤ'),
+    ('v', 'ᵥ'),
+    ('x', 'ₓ'),
+];
+
+fn symbol(name: &str) -> Option<&'static str> {
+    let s = match name {
+        "alpha" => "α",
+        "beta" => "β",
+        "gamma" => "γ",
+        "delta" => "δ",
+        "epsilon" | "varepsilon" => "ε",
+        "zeta" => "ζ",
+        "eta" => "η",
+        "theta" => "θ",
+        "iota" => "ι",
+        "kappa" => "κ",
+        "lambda" => "λ",
+        "mu" => "μ",
+        "nu" => "ν",
+        "xi" => "ξ",
+        "pi" => "π",
+        "rho" => "ρ",
+        "sigma" => "σ",
+        "tau" => "τ",
+        "upsilon" => "υ",
+        "phi" | "varphi" => "φ",
+        "chi" => "χ",
+        "psi" => "ψ",
+        "omega" => "ω",
+        "Gamma" => "Γ",
+        "Delta" => "Δ",
+        "Theta" => "Θ",
+        "Lambda" => "Λ",
+        "Xi" => "Ξ",
+        "Pi" => "Π",
+        "Sigma" => "Σ",
+        "Phi" => "Φ",
+        "Psi" => "Ψ",
+        "Omega" => "Ω",
+        "infty" => "∞",
+        "sum" => "∑",
+        "prod" => "∏",
+        "int" => "∫",
+        "oint" => "∮",
+        "partial" => "∂",
+        "nabla" => "∇",
+        "pm" => "±",
+        "mp" => "∓",
+        "times" => "×",
+        "cdot" => "·",
+        "div" => "÷",
+        "leq" | "le" => "≤",
+        "geq" | "ge" => "≥",
+        "neq" | "ne" => "≠",
+        "approx" => "≈",
+        "equiv" => "≡",
+        "sim" => "∼",
+        "propto" => "∝",
+        "to" | "rightarrow" => "→",
+        "leftarrow" => "←",
+        "Rightarrow" | "implies" => "⇒",
+        "Leftrightarrow" | "iff" => "⇔",
+        "mapsto" => "↦",
+        "in" => "∈",
+        "notin" => "∉",
+        "subset" => "⊂",
+        "subseteq" => "⊆",
+        "cup" => "∪",
+        "cap" => "∩",
+        "emptyset" => "∅",
+        "forall" => "∀",
+        "exists" => "∃",
+        "neg" | "lnot" => "¬",
+        "land" | "wedge" => "∧",
+        "lor" | "vee" => "∨",
+        "ldots" | "dots" => "…",
+        "cdots" => "⋯",
+        "circ" => "∘",
+        "degree" => "°",
+        "langle" => "⟨",
+        "rangle" => "⟩",
+        "quad" => "  ",
+        "qquad" => "    ",
+        _ => return None,
+    };
+    Some(s)
+}
+
+fn double_struck(c: char) -> char {
+    match c {
+        'C' => 'ℂ',
+        'N' => 'ℕ',
+        'P' => 'ℙ',
+        'Q' => 'ℚ',
+        'R' => 'ℝ',
+        'Z' => 'ℤ',
+        c => c,
+    }
+}
+
+/// Script text mapped to Unicode super/subscripts, when some character has no
+/// such form the text is kept with the marker, e.g. `^(α)`.
+fn script(text: &str, table: &[(char, char)], marker: char) -> String {
+    let mapped: Option<String> = text
+        .chars()
+        .map(|c| table.iter().find(|(from, _)| *from == c).map(|(_, to)| *to))
+        .collect();
+    match mapped {
+        Some(s) => s,
+        None if text.chars().count() == 1 => format!("{}{}", marker, text),
+        None => format!("{}({})", marker, text),
+    }
+}
+
+/// Wrap multi-character operand in parentheses so `\frac{a+b}{2}` stays readable.
+fn group(text: &str) -> String {
+    if text.chars().count() <= 1 || text.chars().all(char::is_alphanumeric) {
+        text.to_string()
+    } else {
+        format!("({})", text)
+    }
+}
+
+struct Parser<'a> {
+    chars: Peekable<Chars<'a>>,
+}
+
+impl Parser<'_> {
+    fn parse_group(&mut self, close: Option<char>) -> String {
+        let mut out = String::new();
+        while let Some(c) = self.chars.next() {
+            if Some(c) == close {
+                break;
+            }
+            match c {
+                '{' => out.push_str(&self.parse_group(Some('}'))),
+                '}' => {}
+                '^' => {
+                    let arg = self.parse_argument();
+                    out.push_str(&script(&arg, SUPERSCRIPTS, '^'));
+                }
+                '_' => {
+                    let arg = self.parse_argument();
+                    out.push_str(&script(&arg, SUBSCRIPTS, '_'));
+                }
+                '\\' => out.push_str(&self.parse_command()),
+                c => out.push(c),
+            }
+        }
+        out
+    }
+
+    /// Single argument of command or script, either `{group}`, command or character.
+    fn parse_argument(&mut self) -> String {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+        match self.chars.next() {
+            Some('{') => self.parse_group(Some('}')),
+            Some('\\') => self.parse_command(),
+            Some(c) => c.to_string(),
+            None => String::new(),
+        }
+    }
+
+    fn parse_command(&mut self) -> String {
+        let mut name = String::new();
+        while let Some(c) = self.chars.next_if(char::is_ascii_alphabetic) {
+            name.push(c);
+        }
+        if name.is_empty() {
+            return match self.chars.next() {
+                Some('\\') => "\n".to_string(),
+                Some(',' | ';' | ':' | '!' | ' ') => " ".to_string(),
+                Some(c) => c.to_string(),
+                None => String::new(),
+            };
+        }
+        match name.as_str() {
+            "frac" | "dfrac" | "tfrac" => {
+                let numerator = self.parse_argument();
+                let denominator = self.parse_argument();
+                format!("{}/{}", group(&numerator), group(&denominator))
+            }
+            "sqrt" => format!("√{}", group(&self.parse_argument())),
+            "mathbb" => self.parse_argument().chars().map(double_struck).collect(),
+            "text" | "mathrm" | "mathit" | "mathbf" | "operatorname" => self.parse_argument(),
+            "left" | "right" => String::new(),
+            name => symbol(name)
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("\\{}", name)),
+        }
+    }
+}
+
+/// Convert simple LaTeX math (Greek letters, operators, fractions, super and
+/// subscripts) to Unicode text, unknown commands are kept as written.
+pub fn latex_to_unicode(source: &str) -> String {
+    let mut parser = Parser {
+        chars: source.chars().peekable(),
+    };
+    parser.parse_group(None)
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_symbols_and_scripts() {
+        assert_eq!(latex_to_unicode(r"E = mc^2"), "E = mc²");
+        assert_eq!(
+            latex_to_unicode(r"\alpha_{i+1} \leq \beta^{n}"),
+            "αᵢ₊₁ ≤ βⁿ"
+        );
+        assert_eq!(latex_to_unicode(r"\sum_{i=0}^{n} x_i"), "∑ᵢ₌₀ⁿ xᵢ");
+    }
+
+    #[test]
+    fn test_fractions_and_roots() {
+        assert_eq!(latex_to_unicode(r"\frac{1}{2}"), "1/2");
+        assert_eq!(
+            latex_to_unicode(r"x = \frac{-b \pm \sqrt{b^2 - 4ac}}{2a}"),
+            "x = (-b ± √(b² - 4ac))/2a"
+        );
+    }
+
+    #[test]
+    fn test_unmappable_script_and_unknown_command() {
+        assert_eq!(latex_to_unicode(r"e^{i\pi}"), "e^(iπ)");
+        assert_eq!(latex_to_unicode(r"x \in \mathbb{R}, \foo"), "x ∈ ℝ, \\foo");
+    }
+}
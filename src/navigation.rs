@@ -0,0 +1,277 @@
+use crate::{config::NavigationConfig, enums::SlideJson, history::History};
+
+/// Where a navigation key leads.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Step {
+    Slide(usize),
+    /// End of deck screen after the last slide.
+    End,
+}
+
+fn section(slides: &[SlideJson], index: usize) -> Option<(usize, usize)> {
+    slides.get(index)?.section
+}
+
+fn is_hidden(slides: &[SlideJson], index: usize) -> bool {
+    slides
+        .get(index)
+        .is_some_and(|slide| slide.hidden == Some(true))
+}
+
+/// First slide of section `section` (counted from 1).
+fn section_start(slides: &[SlideJson], section: usize) -> Option<usize> {
+    slides
+        .iter()
+        .position(|slide| slide.section.is_some_and(|(s, _)| s == section))
+}
+
+/// Slides Next and Previous go through, those not `hidden` and only the section
+/// slides in decks with sub-slides.
+pub fn flow_slides(slides: &[SlideJson]) -> Vec<usize> {
+    slides
+        .iter()
+        .enumerate()
+        .filter(|(_, slide)| {
+            slide.hidden != Some(true) && slide.section.is_none_or(|(_, sub)| sub == 0)
+        })
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Next from `current`. On the last slide (or section) it wraps to the first one, or
+/// without loop stops there, showing end of deck screen when configured.
+pub fn next_slide(
+    slides: &[SlideJson],
+    current: usize,
+    navigation: &NavigationConfig,
+) -> Option<Step> {
+    let flow = flow_slides(slides);
+    match flow.iter().copied().find(|&i| i > current) {
+        Some(index) => Some(Step::Slide(index)),
+        None if navigation.wrap => flow.first().copied().map(Step::Slide),
+        None => navigation.end_screen.then_some(Step::End),
+    }
+}
+
+/// Previous from `current`, sub-slides go to the section before their own.
+pub fn previous_slide(slides: &[SlideJson], current: usize, wrap: bool) -> Option<usize> {
+    let current = match section(slides, current) {
+        Some((section, _)) => section_start(slides, section).unwrap_or(0),
+        None => current,
+    };
+    let flow = flow_slides(slides);
+    match flow.iter().copied().rev().find(|&i| i < current) {
+        Some(index) => Some(index),
+        None if wrap => flow.last().copied(),
+        None => None,
+    }
+}
+
+/// Slide below in the same section, Next without sub-slides.
+pub fn down_slide(
+    slides: &[SlideJson],
+    current: usize,
+    navigation: &NavigationConfig,
+) -> Option<Step> {
+    let Some((section_of, _)) = section(slides, current) else {
+        return next_slide(slides, current, navigation);
+    };
+    (current + 1..slides.len())
+        .take_while(|&i| section(slides, i).is_some_and(|(s, _)| s == section_of))
+        .find(|&i| !is_hidden(slides, i))
+        .map(Step::Slide)
+}
+
+/// Slide above in the same section, Previous without sub-slides.
+pub fn up_slide(slides: &[SlideJson], current: usize, wrap: bool) -> Option<usize> {
+    let Some((section_of, _)) = section(slides, current) else {
+        return previous_slide(slides, current, wrap);
+    };
+    (0..current)
+        .rev()
+        .take_while(|&i| section(slides, i).is_some_and(|(s, _)| s == section_of))
+        .find(|&i| !is_hidden(slides, i))
+}
+
+/// Where `count` presses of a navigation key lead, `step` is the key pressed once.
+/// Stepping stops on the last slide and on slides with `confirm_advance`, so a count
+/// does not skip a slide which asks before leaving.
+pub fn count_steps(
+    slides: &[SlideJson],
+    current: usize,
+    count: usize,
+    step: impl Fn(usize) -> Option<Step>,
+) -> Option<Step> {
+    let mut reached = None;
+    let mut index = current;
+    for _ in 0..count {
+        match step(index) {
+            Some(Step::Slide(next)) if next != index => {
+                index = next;
+                reached = Some(Step::Slide(next));
+                if slides[next].confirm_advance == Some(true) {
+                    break;
+                }
+            }
+            Some(Step::End) => return Some(Step::End),
+            _ => break,
+        }
+    }
+    reached
+}
+
+/// Jump from `current` to `index` (go to number, search, overview, ...), the slide
+/// left is kept in history.
+pub fn jump(
+    history: &mut History,
+    slide_count: usize,
+    current: usize,
+    index: usize,
+) -> Option<usize> {
+    if index == current || index >= slide_count {
+        return None;
+    }
+    history.visit(current);
+    Some(index)
+}
+
+/// Slide shown to the audience as `number`, the section in decks with sub-slides.
+/// Numbers past the end give the last one.
+pub fn numbered_slide(slides: &[SlideJson], number: usize) -> Option<usize> {
+    match section(slides, slides.len().checked_sub(1)?) {
+        Some((sections, _)) => section_start(slides, number.clamp(1, sections)),
+        None => Some(number.clamp(1, slides.len()) - 1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    fn deck(count: usize) -> Vec<SlideJson> {
+        (0..count)
+            .map(|i| SlideJson::new(Some(format!("slide {}", i + 1)), vec![]))
+            .collect()
+    }
+
+    /// Sections 1 and 3 without sub-slides, section 2 with two.
+    fn sections() -> Vec<SlideJson> {
+        let mut slides = deck(5);
+        for (slide, section) in slides
+            .iter_mut()
+            .zip([(1, 0), (2, 0), (2, 1), (2, 2), (3, 0)])
+        {
+            slide.section = Some(section);
+        }
+        slides
+    }
+
+    fn navigation(wrap: bool, end_screen: bool) -> NavigationConfig {
+        NavigationConfig { wrap, end_screen }
+    }
+
+    #[test]
+    fn test_hidden_slides() {
+        let mut slides = deck(4);
+        slides[1].hidden = Some(true);
+        let wrap = navigation(true, false);
+        assert_eq!(flow_slides(&slides), vec![0, 2, 3]);
+        assert_eq!(next_slide(&slides, 0, &wrap), Some(Step::Slide(2)));
+        assert_eq!(previous_slide(&slides, 2, true), Some(0));
+        // -- hidden slide reached by a jump goes on in the flow
+        assert_eq!(next_slide(&slides, 1, &wrap), Some(Step::Slide(2)));
+    }
+
+    #[test]
+    fn test_sections() {
+        let mut slides = sections();
+        let wrap = navigation(true, false);
+        assert_eq!(flow_slides(&slides), vec![0, 1, 4]);
+        assert_eq!(down_slide(&slides, 1, &wrap), Some(Step::Slide(2)));
+        assert_eq!(down_slide(&slides, 3, &wrap), None);
+        assert_eq!(up_slide(&slides, 3, true), Some(2));
+        assert_eq!(up_slide(&slides, 1, true), None);
+        // -- sub-slides go along sections with Next and Previous
+        assert_eq!(next_slide(&slides, 2, &wrap), Some(Step::Slide(4)));
+        assert_eq!(previous_slide(&slides, 3, true), Some(0));
+
+        slides[2].hidden = Some(true);
+        assert_eq!(down_slide(&slides, 1, &wrap), Some(Step::Slide(3)));
+        assert_eq!(up_slide(&slides, 3, true), Some(1));
+        // -- without sections Up and Down are Previous and Next
+        assert_eq!(down_slide(&deck(2), 0, &wrap), Some(Step::Slide(1)));
+        assert_eq!(up_slide(&deck(2), 1, true), Some(0));
+    }
+
+    #[test]
+    fn test_loop_and_end_screen() {
+        let slides = deck(3);
+        assert_eq!(
+            next_slide(&slides, 2, &navigation(true, false)),
+            Some(Step::Slide(0))
+        );
+        assert_eq!(previous_slide(&slides, 0, true), Some(2));
+        assert_eq!(next_slide(&slides, 2, &navigation(false, false)), None);
+        assert_eq!(
+            next_slide(&slides, 2, &navigation(false, true)),
+            Some(Step::End)
+        );
+        assert_eq!(previous_slide(&slides, 0, false), None);
+        // -- the last section stops too
+        assert_eq!(
+            next_slide(&sections(), 4, &navigation(false, true)),
+            Some(Step::End)
+        );
+    }
+
+    #[test]
+    fn test_count_motions() {
+        let mut slides = deck(10);
+        slides[3].hidden = Some(true);
+        let stop = navigation(false, false);
+        let next = |i| next_slide(&slides, i, &stop);
+        assert_eq!(count_steps(&slides, 0, 5, next), Some(Step::Slide(6)));
+        assert_eq!(count_steps(&slides, 7, 5, next), Some(Step::Slide(9)));
+        assert_eq!(count_steps(&slides, 9, 5, next), None);
+        let previous = |i| previous_slide(&slides, i, false).map(Step::Slide);
+        assert_eq!(count_steps(&slides, 5, 3, previous), Some(Step::Slide(1)));
+
+        let end = navigation(false, true);
+        let next = |i| next_slide(&slides, i, &end);
+        assert_eq!(count_steps(&slides, 8, 5, next), Some(Step::End));
+
+        let mut confirm = deck(10);
+        confirm[4].confirm_advance = Some(true);
+        let next = |i| next_slide(&confirm, i, &stop);
+        assert_eq!(count_steps(&confirm, 1, 5, next), Some(Step::Slide(4)));
+
+        let sections = sections();
+        let wrap = navigation(true, false);
+        let down = |i| down_slide(&sections, i, &wrap);
+        assert_eq!(count_steps(&sections, 1, 5, down), Some(Step::Slide(3)));
+
+        assert_eq!(numbered_slide(&slides, 12), Some(9));
+        assert_eq!(numbered_slide(&slides, 4), Some(3));
+        assert_eq!(numbered_slide(&slides, 0), Some(0));
+        assert_eq!(numbered_slide(&sections, 3), Some(4));
+        assert_eq!(numbered_slide(&sections, 2), Some(1));
+        assert_eq!(numbered_slide(&[], 2), None);
+    }
+
+    #[test]
+    fn test_history() {
+        let slides = deck(10);
+        let mut history = History::default();
+        assert_eq!(jump(&mut history, slides.len(), 2, 2), None);
+        assert_eq!(jump(&mut history, slides.len(), 2, 10), None);
+        assert_eq!(history.back(2), None);
+
+        // -- `12G` jumps, going back returns to the slide it was typed on
+        let index = numbered_slide(&slides, 12).unwrap();
+        assert_eq!(jump(&mut history, slides.len(), 2, index), Some(9));
+        assert_eq!(history.back(9), Some(2));
+        assert_eq!(history.forward(2), Some(9));
+    }
+}
@@ -0,0 +1,82 @@
+use std::{
+    fs::OpenOptions,
+    io::Write,
+    path::{Path, PathBuf},
+};
+
+use crate::error::{io, Result};
+
+/// Session notes are stored next to the slides file as markdown checklist.
+pub fn notes_path(json_slides: &str) -> PathBuf {
+    let path = Path::new(json_slides);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("slides");
+    path.with_file_name(format!("{}.notes.md", stem))
+}
+
+/// Append note captured on slide (1-based) with current local time.
+pub fn append_note(json_slides: &str, slide: usize, text: &str) -> Result<()> {
+    let time = chrono::Local::now().format("%Y-%m-%d %H:%M:%S");
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(notes_path(json_slides))
+        .map_err(io("failed to open notes file"))?;
+    writeln!(file, "- [ ] {} (slide {}) {}", time, slide, text.trim())?;
+    Ok(())
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub answered: bool,
+    pub text: String,
+}
+
+fn parse_note(line: &str) -> Option<Note> {
+    if let Some(text) = line.strip_prefix("- [ ] ") {
+        return Some(Note {
+            answered: false,
+            text: text.to_string(),
+        });
+    }
+    let text = line
+        .strip_prefix("- [x] ")
+        .or_else(|| line.strip_prefix("- [X] "))?;
+    Some(Note {
+        answered: true,
+        text: text.to_string(),
+    })
+}
+
+/// Notes captured during the session, missing file means no notes.
+pub fn read_notes(json_slides: &str) -> Vec<Note> {
+    std::fs::read_to_string(notes_path(json_slides))
+        .map(|content| content.lines().filter_map(parse_note).collect())
+        .unwrap_or_default()
+}
+
+/// Flip answered state of note at `index` (in order of `read_notes`).
+pub fn toggle_answered(json_slides: &str, index: usize) -> Result<()> {
+    let path = notes_path(json_slides);
+    let content = std::fs::read_to_string(&path).map_err(io("failed to read notes file"))?;
+    let mut note_index = 0;
+    let mut lines = vec![];
+    for line in content.lines() {
+        match parse_note(line) {
+            Some(note) => {
+                if note_index == index {
+                    let mark = if note.answered { " " } else { "x" };
+                    lines.push(format!("- [{}] {}", mark, note.text));
+                } else {
+                    lines.push(line.to_string());
+                }
+                note_index += 1;
+            }
+            None => lines.push(line.to_string()),
+        }
+    }
+    std::fs::write(path, lines.join("\n") + "\n").map_err(io("failed to write notes file"))?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use std::io::{stdout, Write};
+
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crossterm::terminal::SetTitle;
+
+use crate::error::Result;
+
+/// Control characters would end the escape sequence early.
+fn sanitize(text: &str) -> String {
+    text.chars().filter(|c| !c.is_control()).collect()
+}
+
+/// Set terminal window title (OSC 2), also shown by tmux with `set-titles on`.
+pub fn set_title(title: &str) -> Result<()> {
+    crossterm::execute!(stdout(), SetTitle(sanitize(title)))?;
+    Ok(())
+}
+
+/// Put text to system clipboard through the terminal (OSC 52), works over SSH too.
+/// tmux forwards it with `set-clipboard on`.
+pub fn copy(text: &str) -> Result<()> {
+    let mut out = stdout();
+    write!(out, "\x1b]52;c;{}\x07", STANDARD.encode(text))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Desktop notification through the terminal (OSC 9), supported by iTerm2, kitty,
+/// WezTerm and others, terminals without support ignore it.
+pub fn notify(message: &str) -> Result<()> {
+    let mut out = stdout();
+    write!(out, "\x1b]9;{}\x07", sanitize(message))?;
+    out.flush()?;
+    Ok(())
+}
+
+/// Desktop notification with title (OSC 777), supported by urxvt, foot, WezTerm
+/// and ghostty.
+pub fn notify_with_title(title: &str, message: &str) -> Result<()> {
+    let mut out = stdout();
+    write!(
+        out,
+        "\x1b]777;notify;{};{}\x07",
+        sanitize(title).replace(';', ","),
+        sanitize(message)
+    )?;
+    out.flush()?;
+    Ok(())
+}
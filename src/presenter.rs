@@ -0,0 +1,264 @@
+use std::{
+    os::unix::fs::MetadataExt,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use serde::{Deserialize, Serialize};
+use tokio::{
+    io::{AsyncBufReadExt, AsyncWriteExt, BufReader},
+    net::{UnixListener, UnixStream},
+    sync::watch,
+    task::JoinHandle,
+};
+
+use crate::{
+    diagrams::content_hash,
+    error::{io, Result},
+};
+
+/// Presenter view tries to connect again after this long.
+const RECONNECT_DELAY: Duration = Duration::from_secs(1);
+
+/// Where the talk is, sent by the presenting instance to presenter views as JSON line.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct PresenterState {
+    pub slide: usize,
+    /// Unix time the talk clock started, in seconds, `None` before the first slide
+    /// change.
+    pub started: Option<u64>,
+}
+
+/// Socket of the deck in temporary directory, the same for every instance showing it.
+pub fn socket_path(json_slides: &str) -> PathBuf {
+    let deck = std::fs::canonicalize(json_slides).unwrap_or_else(|_| PathBuf::from(json_slides));
+    std::env::temp_dir().join(format!(
+        "tui-slides-{:016x}.sock",
+        content_hash(&deck.to_string_lossy())
+    ))
+}
+
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or_default()
+}
+
+/// Seconds ahead of `duration_secs` budgets of slides (behind when negative) at slide
+/// `index` after `elapsed`. Inside the budget of the slide the talk is on time, `None`
+/// when no slide has a budget.
+pub fn pace(budgets: &[Option<u64>], index: usize, elapsed: Duration) -> Option<i64> {
+    if budgets.iter().all(Option::is_none) {
+        return None;
+    }
+    let start: u64 = budgets.iter().take(index).flatten().sum();
+    let end = start + budgets.get(index).copied().flatten().unwrap_or(0);
+    let elapsed = elapsed.as_secs();
+    Some(if elapsed < start {
+        (start - elapsed) as i64
+    } else if elapsed > end {
+        -((elapsed - end) as i64)
+    } else {
+        0
+    })
+}
+
+fn parse_state(line: &str) -> Option<PresenterState> {
+    serde_json::from_str(line.trim()).ok()
+}
+
+/// Socket of the presenting instance, sends state to each presenter view connected to
+/// it. The socket file is removed when dropped, unless another instance has taken its
+/// place.
+pub struct PresenterServer {
+    tx: watch::Sender<PresenterState>,
+    task: JoinHandle<()>,
+    path: PathBuf,
+    /// Device and inode of the socket file bound.
+    file_id: Option<(u64, u64)>,
+}
+
+fn file_id(path: &Path) -> Option<(u64, u64)> {
+    let metadata = std::fs::symlink_metadata(path).ok()?;
+    Some((metadata.dev(), metadata.ino()))
+}
+
+impl PresenterServer {
+    /// Serve on `path`, fails when another instance presenting the deck answers there.
+    pub fn bind(path: &Path, state: PresenterState) -> Result<Self> {
+        if std::os::unix::net::UnixStream::connect(path).is_ok() {
+            return Err(io("failed to bind presenter socket")(std::io::Error::new(
+                std::io::ErrorKind::AddrInUse,
+                "another instance is presenting the deck",
+            )));
+        }
+        // -- socket left by crashed instance refuses bind
+        let _ = std::fs::remove_file(path);
+        let listener = UnixListener::bind(path).map_err(io("failed to bind presenter socket"))?;
+        let (tx, rx) = watch::channel(state);
+        let task = tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                tokio::spawn(send_states(stream, rx.clone()));
+            }
+        });
+        Ok(Self {
+            tx,
+            task,
+            path: path.to_path_buf(),
+            file_id: file_id(path),
+        })
+    }
+
+    pub fn send(&self, state: PresenterState) {
+        self.tx.send_replace(state);
+    }
+}
+
+impl Drop for PresenterServer {
+    fn drop(&mut self) {
+        self.task.abort();
+        if self.file_id.is_some() && file_id(&self.path) == self.file_id {
+            let _ = std::fs::remove_file(&self.path);
+        }
+    }
+}
+
+async fn send_states(mut stream: UnixStream, mut rx: watch::Receiver<PresenterState>) {
+    loop {
+        let state = *rx.borrow_and_update();
+        let Ok(line) = serde_json::to_string(&state) else {
+            return;
+        };
+        if stream
+            .write_all(format!("{line}\n").as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+        if rx.changed().await.is_err() {
+            return;
+        }
+    }
+}
+
+/// Presenter view connection, follows state of the presenting instance and connects
+/// again when it restarts. Stops when dropped.
+pub struct PresenterFollower {
+    state: Arc<Mutex<Option<PresenterState>>>,
+    task: JoinHandle<()>,
+}
+
+impl PresenterFollower {
+    pub fn connect(path: PathBuf) -> Self {
+        let state = Arc::new(Mutex::new(None));
+        let task_state = state.clone();
+        let task = tokio::spawn(async move {
+            loop {
+                if let Ok(stream) = UnixStream::connect(&path).await {
+                    let mut lines = BufReader::new(stream).lines();
+                    while let Ok(Some(line)) = lines.next_line().await {
+                        if let (Some(received), Ok(mut state)) =
+                            (parse_state(&line), task_state.lock())
+                        {
+                            *state = Some(received);
+                        }
+                    }
+                    if let Ok(mut state) = task_state.lock() {
+                        *state = None;
+                    }
+                }
+                tokio::time::sleep(RECONNECT_DELAY).await;
+            }
+        });
+        Self { state, task }
+    }
+
+    /// Last state received, `None` while not connected.
+    pub fn state(&self) -> Option<PresenterState> {
+        self.state.lock().ok().and_then(|state| *state)
+    }
+}
+
+impl Drop for PresenterFollower {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_socket_path_and_state() {
+        let path = socket_path("/nonexistent/talk.json");
+        assert_eq!(path, socket_path("/nonexistent/talk.json"));
+        assert_ne!(path, socket_path("/nonexistent/other.json"));
+        assert!(path.to_string_lossy().ends_with(".sock"));
+
+        let state = PresenterState {
+            slide: 3,
+            started: Some(1_700_000_000),
+        };
+        let line = serde_json::to_string(&state).unwrap();
+        assert_eq!(parse_state(&format!("{line}\n")), Some(state));
+        assert_eq!(parse_state("garbage"), None);
+    }
+
+    #[test]
+    fn test_pace() {
+        let budgets = [Some(60), None, Some(120), Some(60)];
+        let secs = Duration::from_secs;
+        assert_eq!(pace(&budgets, 2, secs(30)), Some(30));
+        assert_eq!(pace(&budgets, 2, secs(100)), Some(0));
+        assert_eq!(pace(&budgets, 2, secs(200)), Some(-20));
+        assert_eq!(pace(&budgets, 3, secs(150)), Some(30));
+        assert_eq!(pace(&[None, None], 1, secs(10)), None);
+    }
+
+    #[tokio::test]
+    async fn test_follower_gets_state() {
+        let path =
+            std::env::temp_dir().join(format!("tui-slides-test-{}.sock", std::process::id()));
+        let server = PresenterServer::bind(&path, PresenterState::default()).unwrap();
+        let follower = PresenterFollower::connect(path.clone());
+        let state = PresenterState {
+            slide: 4,
+            started: Some(10),
+        };
+        server.send(state);
+        for _ in 0..100 {
+            if follower.state() == Some(state) {
+                break;
+            }
+            tokio::time::sleep(Duration::from_millis(20)).await;
+        }
+        assert_eq!(follower.state(), Some(state));
+        drop(server);
+        assert!(!path.exists());
+    }
+
+    #[tokio::test]
+    async fn test_second_instance_keeps_socket() {
+        let path =
+            std::env::temp_dir().join(format!("tui-slides-test-{}-2.sock", std::process::id()));
+        // -- stale socket of crashed instance is replaced
+        drop(std::os::unix::net::UnixListener::bind(&path).unwrap());
+        let first = PresenterServer::bind(&path, PresenterState::default()).unwrap();
+        assert!(PresenterServer::bind(&path, PresenterState::default()).is_err());
+        assert!(path.exists());
+
+        // -- socket taken over by a later instance is left to it
+        std::fs::remove_file(&path).unwrap();
+        let second = PresenterServer::bind(&path, PresenterState::default()).unwrap();
+        drop(first);
+        assert!(path.exists());
+        drop(second);
+        assert!(!path.exists());
+    }
+}
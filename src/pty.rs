@@ -0,0 +1,157 @@
+use std::{
+    io::{Read, Write},
+    path::Path,
+    sync::{Arc, Mutex},
+    thread,
+};
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use portable_pty::{native_pty_system, Child, CommandBuilder, MasterPty, PtySize};
+
+use crate::error::{io, Error, Result};
+
+/// Lines kept above the visible screen of embedded terminal.
+const SCROLLBACK: usize = 1000;
+
+/// Command running in a pseudo terminal, its output is parsed into a screen which
+/// is drawn by `TerminalWidget`.
+pub struct PtySession {
+    parser: Arc<Mutex<vt100::Parser>>,
+    master: Box<dyn MasterPty + Send>,
+    writer: Box<dyn Write + Send>,
+    child: Box<dyn Child + Send + Sync>,
+}
+
+/// Bytes a terminal sends for the key.
+fn key_bytes(key: KeyEvent) -> Vec<u8> {
+    let ctrl = key.modifiers.contains(KeyModifiers::CONTROL);
+    let alt = key.modifiers.contains(KeyModifiers::ALT);
+    let mut bytes = match key.code {
+        KeyCode::Char(c) if ctrl && c.is_ascii_alphabetic() => {
+            vec![c.to_ascii_lowercase() as u8 - b'a' + 1]
+        }
+        KeyCode::Char(c) => c.to_string().into_bytes(),
+        KeyCode::Enter => vec![b'\r'],
+        KeyCode::Backspace => vec![0x7f],
+        KeyCode::Tab => vec![b'\t'],
+        KeyCode::BackTab => b"\x1b[Z".to_vec(),
+        KeyCode::Esc => vec![0x1b],
+        KeyCode::Up => b"\x1b[A".to_vec(),
+        KeyCode::Down => b"\x1b[B".to_vec(),
+        KeyCode::Right => b"\x1b[C".to_vec(),
+        KeyCode::Left => b"\x1b[D".to_vec(),
+        KeyCode::Home => b"\x1b[H".to_vec(),
+        KeyCode::End => b"\x1b[F".to_vec(),
+        KeyCode::PageUp => b"\x1b[5~".to_vec(),
+        KeyCode::PageDown => b"\x1b[6~".to_vec(),
+        KeyCode::Delete => b"\x1b[3~".to_vec(),
+        KeyCode::Insert => b"\x1b[2~".to_vec(),
+        KeyCode::F(n @ 1..=4) => vec![0x1b, b'O', b'P' + n - 1],
+        _ => vec![],
+    };
+    if alt && !bytes.is_empty() {
+        bytes.insert(0, 0x1b);
+    }
+    bytes
+}
+
+impl PtySession {
+    /// Run `command` with `sh -c` in `cwd`, empty command starts the default shell.
+    pub fn spawn(command: &str, cwd: &Path, rows: u16, cols: u16) -> Result<Self> {
+        let size = PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        let pair = native_pty_system()
+            .openpty(size)
+            .map_err(|e| Error::Render(format!("failed to open pty: {}", e)))?;
+
+        let mut cmd = if command.trim().is_empty() {
+            CommandBuilder::new_default_prog()
+        } else {
+            let mut cmd = CommandBuilder::new("sh");
+            cmd.args(["-c", command]);
+            cmd
+        };
+        cmd.cwd(cwd);
+        let child = pair
+            .slave
+            .spawn_command(cmd)
+            .map_err(|e| Error::Render(format!("failed to run `{}`: {}", command, e)))?;
+
+        let parser = Arc::new(Mutex::new(vt100::Parser::new(rows, cols, SCROLLBACK)));
+        let mut reader = pair
+            .master
+            .try_clone_reader()
+            .map_err(|e| Error::Protocol(format!("failed to read pty: {}", e)))?;
+        let writer = pair
+            .master
+            .take_writer()
+            .map_err(|e| Error::Protocol(format!("failed to write pty: {}", e)))?;
+
+        let output = parser.clone();
+        thread::spawn(move || {
+            let mut buf = [0u8; 4096];
+            while let Ok(n) = reader.read(&mut buf) {
+                if n == 0 {
+                    break;
+                }
+                if let Ok(mut parser) = output.lock() {
+                    parser.process(&buf[..n]);
+                }
+            }
+        });
+
+        Ok(Self {
+            parser,
+            master: pair.master,
+            writer,
+            child,
+        })
+    }
+
+    /// Shared screen of the terminal, updated from the reader thread.
+    pub fn parser(&self) -> Arc<Mutex<vt100::Parser>> {
+        self.parser.clone()
+    }
+
+    pub fn write_key(&mut self, key: KeyEvent) -> Result<()> {
+        let bytes = key_bytes(key);
+        if bytes.is_empty() {
+            return Ok(());
+        }
+        self.writer
+            .write_all(&bytes)
+            .and_then(|_| self.writer.flush())
+            .map_err(io("failed to write to terminal"))
+    }
+
+    /// Resize the pty and the screen, nothing is done when size did not change.
+    pub fn resize(&mut self, rows: u16, cols: u16) {
+        let Ok(mut parser) = self.parser.lock() else {
+            return;
+        };
+        if parser.screen().size() == (rows, cols) {
+            return;
+        }
+        parser.set_size(rows, cols);
+        let size = PtySize {
+            rows,
+            cols,
+            pixel_width: 0,
+            pixel_height: 0,
+        };
+        if let Err(e) = self.master.resize(size) {
+            log::error!("Failed to resize terminal: {e}");
+        }
+    }
+}
+
+impl Drop for PtySession {
+    fn drop(&mut self) {
+        let _ = self.child.kill();
+        let _ = self.child.wait();
+    }
+}
@@ -0,0 +1,109 @@
+use std::{
+    collections::BTreeMap,
+    path::{Path, PathBuf},
+    time::{Duration, Instant},
+};
+
+use crate::{
+    enums::SlideJson,
+    error::{io, Result},
+};
+
+/// Rehearsal report is written next to the slides file as CSV.
+pub fn report_path(json_slides: &str) -> PathBuf {
+    let path = Path::new(json_slides);
+    let stem = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("slides");
+    path.with_file_name(format!("{}.rehearsal.csv", stem))
+}
+
+/// Time spent on each slide during `--rehearse`, slides visited again add up.
+#[derive(Debug, Default)]
+pub struct Rehearsal {
+    current: Option<(usize, Instant)>,
+    spent: BTreeMap<usize, Duration>,
+}
+
+impl Rehearsal {
+    /// Slide `index` is shown, time of the slide shown before is recorded.
+    pub fn enter(&mut self, index: usize) {
+        if self.current.is_some_and(|(current, _)| current == index) {
+            return;
+        }
+        self.finish();
+        self.current = Some((index, Instant::now()));
+    }
+
+    /// Record time of the slide shown, e.g. on exit.
+    pub fn finish(&mut self) {
+        if let Some((index, since)) = self.current.take() {
+            self.record(index, since.elapsed());
+        }
+    }
+
+    pub fn record(&mut self, index: usize, spent: Duration) {
+        *self.spent.entry(index).or_default() += spent;
+    }
+
+    /// CSV with slide number, title, seconds spent, `duration_secs` budget and
+    /// seconds over it, for slides shown.
+    pub fn to_csv(&self, slides: &[SlideJson]) -> String {
+        let mut csv = String::from("slide,title,seconds,budget,over\n");
+        for (&index, spent) in &self.spent {
+            let slide = slides.get(index);
+            let title = slide.and_then(|s| s.title.as_deref()).unwrap_or_default();
+            let budget = slide.and_then(|s| s.duration_secs);
+            let seconds = spent.as_secs();
+            csv.push_str(&format!(
+                "{},\"{}\",{},{},{}\n",
+                index + 1,
+                title.replace('"', "\"\""),
+                seconds,
+                budget.map(|b| b.to_string()).unwrap_or_default(),
+                budget
+                    .map(|b| seconds.saturating_sub(b).to_string())
+                    .unwrap_or_default()
+            ));
+        }
+        csv
+    }
+
+    pub fn write(&mut self, json_slides: &str, slides: &[SlideJson]) -> Result<PathBuf> {
+        self.finish();
+        let path = report_path(json_slides);
+        std::fs::write(&path, self.to_csv(slides))
+            .map_err(io("failed to write rehearsal report"))?;
+        Ok(path)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_rehearsal_csv() {
+        let mut intro = SlideJson::new(Some("Intro".to_string()), vec![]);
+        intro.duration_secs = Some(60);
+        let slides = vec![
+            intro,
+            SlideJson::new(Some("Say \"hi\"".to_string()), vec![]),
+        ];
+        let mut rehearsal = Rehearsal::default();
+        rehearsal.record(0, Duration::from_secs(50));
+        rehearsal.record(1, Duration::from_secs(30));
+        rehearsal.record(0, Duration::from_secs(25));
+        assert_eq!(
+            rehearsal.to_csv(&slides),
+            "slide,title,seconds,budget,over\n1,\"Intro\",75,60,15\n2,\"Say \"\"hi\"\"\",30,,\n"
+        );
+        assert_eq!(
+            report_path("talks/async.json5"),
+            PathBuf::from("talks/async.rehearsal.csv")
+        );
+    }
+}
@@ -0,0 +1,161 @@
+use crate::enums::{ContentJson, SlideJson};
+
+fn push_item_text(item: &ContentJson, text: &mut Vec<String>) {
+    text.extend(item.content.clone());
+    text.extend(item.header.iter().flatten().cloned());
+    text.extend(item.rows.iter().flatten().flatten().cloned());
+    text.extend(item.slices.iter().flatten().map(|s| s.label.clone()));
+    text.extend(item.options.iter().flatten().map(|o| o.label.clone()));
+    for callout in item.callouts.iter().flatten() {
+        text.push(callout.text.clone());
+    }
+    for tab in item.tabs.iter().flatten() {
+        text.push(tab.title.clone());
+        for item in &tab.content {
+            push_item_text(item, text);
+        }
+    }
+}
+
+/// Text of slide searched by `/`: title and the strings of all items, tabs included.
+pub fn slide_text(slide: &SlideJson) -> String {
+    let mut text: Vec<String> = slide.title.iter().cloned().collect();
+    for item in &slide.content {
+        push_item_text(item, &mut text);
+    }
+    text.join("\n")
+}
+
+/// Indexes of slides containing `query`, case is ignored.
+pub fn matching_slides(slides: &[SlideJson], query: &str) -> Vec<usize> {
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return vec![];
+    }
+    slides
+        .iter()
+        .enumerate()
+        .filter(|(_, slide)| slide_text(slide).to_lowercase().contains(&query))
+        .map(|(index, _)| index)
+        .collect()
+}
+
+/// Match after `current` slide (before it when `backwards`), wrapping around the deck.
+pub fn next_match(matches: &[usize], current: usize, backwards: bool) -> Option<usize> {
+    if backwards {
+        matches
+            .iter()
+            .rev()
+            .find(|&&m| m < current)
+            .or(matches.last())
+            .copied()
+    } else {
+        matches
+            .iter()
+            .find(|&&m| m > current)
+            .or(matches.first())
+            .copied()
+    }
+}
+
+/// Index of slide with `id`.
+pub fn slide_with_id(slides: &[SlideJson], id: &str) -> Option<usize> {
+    slides
+        .iter()
+        .position(|slide| slide.id.as_deref() == Some(id))
+}
+
+/// Score of `text` containing the characters of `query` in order, higher when they
+/// are consecutive or start words. `None` when some character is missing.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<i64> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let found = position + text[position..].iter().position(|&t| t == c)?;
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == found) {
+            score += 5;
+        }
+        if found == 0 || !text[found - 1].is_alphanumeric() {
+            score += 3;
+        }
+        previous = Some(found);
+        position = found + 1;
+    }
+    Some(score)
+}
+
+/// Indexes of slides with title matching `query` by [`fuzzy_score`], best first.
+pub fn fuzzy_titles(slides: &[SlideJson], query: &str) -> Vec<usize> {
+    let mut scored: Vec<(i64, usize)> = slides
+        .iter()
+        .enumerate()
+        .filter_map(|(index, slide)| {
+            let title = slide.title.as_deref().unwrap_or_default();
+            Some((fuzzy_score(query, title)?, index))
+        })
+        .collect();
+    scored.sort_by(|(a, i), (b, j)| b.cmp(a).then(i.cmp(j)));
+    scored.into_iter().map(|(_, index)| index).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+    use crate::enums::{SlideContentType, TabJson};
+
+    #[test]
+    fn test_matching_slides() {
+        let paragraph = |text: &str| ContentJson {
+            type_: SlideContentType::Paragraph,
+            content: Some(text.to_string()),
+            ..Default::default()
+        };
+        let tabs = ContentJson {
+            type_: SlideContentType::Tabs,
+            tabs: Some(vec![TabJson {
+                title: "Queues".to_string(),
+                content: vec![paragraph("Bounded channels give BACKPRESSURE")],
+                extra: Default::default(),
+            }]),
+            ..Default::default()
+        };
+        let slides = vec![
+            SlideJson::new(Some("Intro".to_string()), vec![paragraph("hello")]),
+            SlideJson::new(Some("Backpressure".to_string()), vec![]),
+            SlideJson::new(None, vec![tabs]),
+        ];
+        assert_eq!(matching_slides(&slides, "backpressure"), vec![1, 2]);
+        assert_eq!(matching_slides(&slides, "queues"), vec![2]);
+        assert!(matching_slides(&slides, " ").is_empty());
+
+        assert_eq!(next_match(&[1, 2], 1, false), Some(2));
+        assert_eq!(next_match(&[1, 2], 2, false), Some(1));
+        assert_eq!(next_match(&[1, 2], 1, true), Some(2));
+        assert_eq!(next_match(&[], 0, false), None);
+
+        let mut storage = SlideJson::new(Some("Storage".to_string()), vec![]);
+        storage.id = Some("storage".to_string());
+        let slides = vec![slides[0].clone(), storage];
+        assert_eq!(slide_with_id(&slides, "storage"), Some(1));
+        assert_eq!(slide_with_id(&slides, "network"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_titles() {
+        let slides: Vec<SlideJson> = ["Intro", "Async runtime", "Backpressure", "Q&A"]
+            .iter()
+            .map(|title| SlideJson::new(Some(title.to_string()), vec![]))
+            .collect();
+        assert_eq!(fuzzy_titles(&slides, "run"), vec![1]);
+        assert_eq!(fuzzy_titles(&slides, "bp"), vec![2]);
+        assert_eq!(fuzzy_titles(&slides, "r"), vec![1, 0, 2]);
+        assert_eq!(fuzzy_titles(&slides, "").len(), 4);
+        assert!(fuzzy_score("xyz", "Intro").is_none());
+        assert!(fuzzy_score("as", "Async") > fuzzy_score("as", "Backpressure"));
+    }
+}
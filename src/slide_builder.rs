@@ -1,16 +1,36 @@
-use std::{ops::Deref, path::Path, str::FromStr, sync::Arc};
+use std::{ops::Deref, path::Path, str::FromStr, sync::Arc, time::Duration};
 
-use crate::enums::{ContentJson, ReturnSlideWidget, SlideContentType, SlideJson};
-use color_eyre::{eyre::Result, owo_colors::OwoColorize};
+use crate::{
+    diagrams::{cached_diagram, diagram_path, is_diagram, CACHE_DIR},
+    enums::{ContentJson, ReturnSlideWidget, SlideContentType, SlideJson},
+    error::{io, Error, Result},
+    highlight::{ansi_lines, diff_lines, inline_code_lines},
+    loaders::deck_dir,
+    locale::locale,
+    math::latex_to_unicode,
+    theme,
+    widgets::{
+        clock::ClockWidget,
+        countdown::CountdownWidget,
+        icon::IconWidget,
+        pie::{PieChart, PieSlice},
+        plot::{parse_points, PlotWidget},
+        qr::QrWidget,
+        rule::RuleWidget,
+    },
+};
+use color_eyre::owo_colors::OwoColorize;
 use crossterm::terminal::size;
+use image::{codecs::gif::GifDecoder, AnimationDecoder, DynamicImage};
 use ratatui::{
     layout::{Alignment, Rect},
     prelude::*,
     style::Stylize,
+    symbols::border,
     text::Line,
     widgets::{
         block::{self, Title},
-        Block, BorderType, Borders, Paragraph, Sparkline, WidgetRef,
+        Block, BorderType, Borders, Paragraph, Row, Sparkline, Table, Tabs, WidgetRef,
     },
 };
 use ratatui_image::{picker::Picker, Image, Resize, StatefulImage};
@@ -51,8 +71,11 @@ fn get_slide_content_max(slide: &ContentJson) -> u64 {
 fn make_slide_paragraph<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
     let content = get_slide_content_string(&slide);
     let color = get_slide_content_color(&slide);
+    let code_style = Style::default().fg(Color::Yellow).bg(Color::Rgb(40, 40, 40));
     ReturnSlideWidget::Paragraph(
-        Paragraph::new(content).style(Style::default().fg(Color::from_str(&color).unwrap())),
+        Paragraph::new(inline_code_lines(&content, code_style)).style(
+            Style::default().fg(theme::parse_color(&color).unwrap_or(Color::Red)),
+        ),
     )
 }
 
@@ -64,15 +87,28 @@ fn make_slide_line<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
     let color = get_slide_content_color(&slide);
     ReturnSlideWidget::Line(
         Line::from(content)
-            .style(Style::default().fg(Color::from_str(&color).unwrap_or(Color::Blue))),
+            .style(Style::default().fg(theme::parse_color(&color).unwrap_or(Color::Blue))),
     )
 }
 
 // -------------
 // -- BIGTEXT
 // -------------
+/// Big text with FIGlet `font` of the deck theme when set, tui-big-text otherwise.
 fn make_slide_bigtext<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
     let content = get_slide_content_string(&slide);
+    let font = slide.font.as_ref().and_then(|f| theme::theme().fonts.get(f).cloned());
+    if let Some(font) = font {
+        let color = slide
+            .color
+            .as_ref()
+            .and_then(|c| theme::parse_color(c))
+            .unwrap_or(Color::White);
+        let lines: Vec<Line> = font.render(&content).into_iter().map(Line::from).collect();
+        return ReturnSlideWidget::Paragraph(
+            Paragraph::new(lines).fg(color).alignment(Alignment::Center),
+        );
+    }
     let lines: Vec<Line> = content
         .split('\n')
         .map(|s| Line::from(s.to_string()))
@@ -90,15 +126,79 @@ fn make_slide_bigtext<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
 // -------------
 // -- IMAGE
 // -------------
-pub fn make_slide_image<'a>(slide: ContentJson, slide_path: String) -> ReturnSlideWidget<'a> {
-    let f_path = Path::new(&slide_path);
-    let img_path = f_path.parent().unwrap();
-    let content = get_slide_content_string(&slide);
-    let dyn_img = image::io::Reader::open(img_path.join(content))
-        .unwrap()
+pub fn load_image(slide: &ContentJson, slide_path: &str) -> Result<DynamicImage> {
+    let content = get_slide_content_string(slide);
+    let image_error = |source| Error::Image {
+        path: content.clone(),
+        source,
+    };
+    image::io::Reader::open(deck_dir(slide_path).join(&content))
+        .map_err(io(format!("failed to open image '{}'", content)))?
         .decode()
-        .unwrap();
-    ReturnSlideWidget::Image(dyn_img)
+        .map_err(image_error)
+}
+
+/// Image widget, missing or broken image is shown as error text instead.
+pub fn make_slide_image<'a>(slide: ContentJson, slide_path: String) -> ReturnSlideWidget<'a> {
+    match load_image(&slide, &slide_path) {
+        Ok(dyn_img) => ReturnSlideWidget::Image(dyn_img),
+        Err(e) => ReturnSlideWidget::Paragraph(Paragraph::new(e.report()).red()),
+    }
+}
+
+/// Frames of animated GIF image along with their delays, `None` for other images.
+pub fn make_slide_image_frames(
+    slide: &ContentJson,
+    slide_path: &str,
+) -> Option<Vec<(DynamicImage, Duration)>> {
+    let content = get_slide_content_string(slide);
+    if !content.to_lowercase().ends_with(".gif") {
+        return None;
+    }
+    let img_path = deck_dir(slide_path).join(content);
+    let file = std::fs::File::open(img_path).ok()?;
+    let decoder = GifDecoder::new(std::io::BufReader::new(file)).ok()?;
+    let frames = decoder.into_frames().collect_frames().ok()?;
+    if frames.len() < 2 {
+        return None;
+    }
+    Some(
+        frames
+            .into_iter()
+            .map(|f| {
+                let delay = Duration::from(f.delay());
+                (DynamicImage::ImageRgba8(f.into_buffer()), delay)
+            })
+            .collect(),
+    )
+}
+
+// -------------
+// -- DIAGRAM
+// -------------
+/// Image item showing rendered diagram, `None` when item is not a diagram or it is
+/// not rendered.
+pub fn diagram_image_item(slide: &ContentJson, slide_path: &str) -> Option<ContentJson> {
+    if !is_diagram(slide) {
+        return None;
+    }
+    // -- image items are relative to the deck directory
+    let path = Path::new(CACHE_DIR).join(cached_diagram(slide, slide_path)?.file_name()?);
+    Some(ContentJson {
+        type_: SlideContentType::Image,
+        content: Some(path.to_string_lossy().to_string()),
+        rect: slide.rect,
+        ..Default::default()
+    })
+}
+
+fn make_slide_diagram<'a>(slide: ContentJson, slide_path: String) -> ReturnSlideWidget<'a> {
+    match diagram_image_item(&slide, &slide_path) {
+        Some(image) => make_slide_image(image, slide_path),
+        None => ReturnSlideWidget::Paragraph(
+            Paragraph::new(format!("{:?} diagram is not rendered, see log", slide.type_)).red(),
+        ),
+    }
 }
 
 // -------------
@@ -110,7 +210,10 @@ pub fn make_slide_block<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
     ReturnSlideWidget::Block(
         Block::default()
             .borders(Borders::ALL)
-            .border_style(Style::default().fg(Color::from_str(&color).unwrap()))
+            .border_style(
+                Style::default().fg(theme::parse_color(&color).unwrap_or(Color::Red)),
+            )
+            .border_set(theme::theme().border.unwrap_or(border::PLAIN))
             .title(
                 Title::from(Line::from(vec![content.yellow()]))
                     .alignment(Alignment::Right)
@@ -147,7 +250,212 @@ pub fn make_slide_code_highlight<'a>(slide: ContentJson) -> ReturnSlideWidget<'a
     ReturnSlideWidget::CodeHighlight(Paragraph::new(content))
 }
 
- 
+// -------------
+// -- PIE
+// -------------
+pub fn make_slide_pie<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let slices = slide
+        .slices
+        .unwrap_or_default()
+        .into_iter()
+        .enumerate()
+        .map(|(i, s)| {
+            let color = s.color.and_then(|c| theme::parse_color(&c));
+            PieSlice::new(s.label, s.value, color, i)
+        })
+        .collect();
+
+    ReturnSlideWidget::Pie(PieChart::new(slices).donut(slide.donut.unwrap_or(false)))
+}
+
+// -------------
+// -- TABLE
+// -------------
+pub fn make_slide_table<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::White);
+    let header = slide.header.unwrap_or_default();
+    let mut rows = slide.rows.unwrap_or_default();
+    if let Some(locale) = locale() {
+        for cell in rows.iter_mut().flatten() {
+            *cell = locale.cell(cell);
+        }
+    }
+
+    let columns = rows
+        .iter()
+        .map(|r| r.len())
+        .chain(std::iter::once(header.len()))
+        .max()
+        .unwrap_or(0);
+    let widths: Vec<Constraint> = (0..columns)
+        .map(|i| {
+            let width = rows
+                .iter()
+                .chain(std::iter::once(&header))
+                .filter_map(|r| r.get(i))
+                .map(|c| c.chars().count())
+                .max()
+                .unwrap_or(0);
+            Constraint::Length(width as u16)
+        })
+        .collect();
+
+    let mut table = Table::new(rows.into_iter().map(Row::new), widths)
+        .column_spacing(2)
+        .style(Style::default().fg(color));
+    if !header.is_empty() {
+        table = table.header(Row::new(header).style(Style::default().yellow().bold()));
+    }
+    ReturnSlideWidget::Table(table)
+}
+
+
+// -------------
+// -- TABS
+// -------------
+pub fn make_slide_tabs<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let color = get_slide_content_color(&slide);
+    let titles: Vec<String> = slide
+        .tabs
+        .unwrap_or_default()
+        .into_iter()
+        .map(|t| t.title)
+        .collect();
+
+    ReturnSlideWidget::Tabs(
+        Tabs::new(titles)
+            .style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .highlight_style(
+                Style::default()
+                    .fg(theme::parse_color(&color).unwrap_or(Color::Yellow))
+                    .bold(),
+            ),
+    )
+}
+
+// -------------
+// -- QR CODE
+// -------------
+pub fn make_slide_qr_code<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let content = get_slide_content_string(&slide);
+    ReturnSlideWidget::QrCode(QrWidget::new(&content))
+}
+
+// -------------
+// -- MATH
+// -------------
+fn make_slide_math<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let content = latex_to_unicode(&get_slide_content_string(&slide));
+    let color = slide.color.clone().unwrap_or_else(|| String::from("#FFFFFF"));
+    ReturnSlideWidget::Paragraph(
+        Paragraph::new(content)
+            .style(Style::default().fg(theme::parse_color(&color).unwrap_or(Color::White))),
+    )
+}
+
+// -------------
+// -- ICON
+// -------------
+fn make_slide_icon<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let content = get_slide_content_string(&slide);
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::White);
+    ReturnSlideWidget::Icon(IconWidget::new(&content, color))
+}
+
+// -------------
+// -- PLOT
+// -------------
+fn make_slide_plot<'a>(slide: ContentJson, slide_path: String) -> ReturnSlideWidget<'a> {
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::Cyan);
+    let points = match &slide.csv {
+        Some(csv) => {
+            let csv_path = deck_dir(&slide_path).join(csv);
+            match std::fs::read_to_string(&csv_path) {
+                Ok(content) => parse_points(&content),
+                Err(e) => {
+                    return ReturnSlideWidget::Paragraph(
+                        Paragraph::new(format!("Failed to read '{}': {}", csv, e)).red(),
+                    )
+                }
+            }
+        }
+        None => slide
+            .data
+            .unwrap_or_default()
+            .into_iter()
+            .enumerate()
+            .map(|(x, y)| (x as f64, y as f64))
+            .collect(),
+    };
+    ReturnSlideWidget::Plot(PlotWidget::new(points, color).line(slide.line.unwrap_or(false)))
+}
+
+// -------------
+// -- RULE
+// -------------
+fn make_slide_rule<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::Rgb(100, 100, 100));
+    ReturnSlideWidget::Rule(
+        RuleWidget::new(slide.content, color).vertical(slide.vertical.unwrap_or(false)),
+    )
+}
+
+// -------------
+// -- COUNTDOWN
+// -------------
+fn make_slide_countdown<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::Green);
+    let duration = Duration::from_secs(slide.duration.unwrap_or(300));
+    ReturnSlideWidget::Countdown(CountdownWidget::new(duration, color))
+}
+
+// -------------
+// -- CLOCK
+// -------------
+fn make_slide_clock<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let color = slide
+        .color
+        .as_ref()
+        .and_then(|c| theme::parse_color(c))
+        .unwrap_or(Color::White);
+    ReturnSlideWidget::Clock(ClockWidget::new(slide.format, color).big(slide.big.unwrap_or(false)))
+}
+
+// -------------
+// -- DIFF
+// -------------
+fn make_slide_diff<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let content = get_slide_content_string(&slide);
+    ReturnSlideWidget::Paragraph(Paragraph::new(diff_lines(&content)))
+}
+
+// -------------
+// -- ANSI
+// -------------
+fn make_slide_ansi<'a>(slide: ContentJson) -> ReturnSlideWidget<'a> {
+    let content = get_slide_content_string(&slide);
+    ReturnSlideWidget::Paragraph(Paragraph::new(ansi_lines(&content)))
+}
 
 pub fn make_slide_content<'a>(
     slide_content: ContentJson,
@@ -161,5 +469,49 @@ pub fn make_slide_content<'a>(
         SlideContentType::Block => make_slide_block(slide_content),
         SlideContentType::Sparkline => make_slide_sparkline(slide_content),
         SlideContentType::CodeHighlight => make_slide_code_highlight(slide_content),
+        SlideContentType::Pie => make_slide_pie(slide_content),
+        SlideContentType::Table => make_slide_table(slide_content),
+        SlideContentType::Tabs => make_slide_tabs(slide_content),
+        SlideContentType::QrCode => make_slide_qr_code(slide_content),
+        SlideContentType::Questions => ReturnSlideWidget::Questions,
+        SlideContentType::Menu => ReturnSlideWidget::Menu,
+        SlideContentType::Mermaid | SlideContentType::Graphviz => {
+            make_slide_diagram(slide_content, slide_path)
+        }
+        SlideContentType::Math => make_slide_math(slide_content),
+        SlideContentType::Icon => make_slide_icon(slide_content),
+        SlideContentType::Plot => make_slide_plot(slide_content, slide_path),
+        SlideContentType::Rule => make_slide_rule(slide_content),
+        SlideContentType::Countdown => make_slide_countdown(slide_content),
+        SlideContentType::Clock => make_slide_clock(slide_content),
+        SlideContentType::Terminal | SlideContentType::Repl => ReturnSlideWidget::Terminal,
+        SlideContentType::Diff => make_slide_diff(slide_content),
+        SlideContentType::Ansi => make_slide_ansi(slide_content),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_diagram_of_deck_in_subdirectory() {
+        // -- relative like the --json argument, joined onto deck directory once
+        let dir = Path::new("target").join(format!("tui-slides-diagram-{}", std::process::id()));
+        let json_slides = dir.join("talk").join("slides.json5");
+        let json_slides = json_slides.to_string_lossy();
+        let item = ContentJson {
+            type_: SlideContentType::Mermaid,
+            content: Some("graph TD; A-->B".to_string()),
+            ..Default::default()
+        };
+        let path = diagram_path(&item, &json_slides);
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        image::RgbImage::new(2, 2).save(&path).unwrap();
+
+        let image = diagram_image_item(&item, &json_slides).unwrap();
+        let loaded = load_image(&image, &json_slides);
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert_eq!(loaded.unwrap().width(), 2);
     }
 }
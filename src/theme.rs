@@ -0,0 +1,264 @@
+use std::{
+    collections::{HashMap, HashSet},
+    path::Path,
+    str::FromStr,
+    sync::{Arc, Mutex, OnceLock, RwLock},
+};
+
+use ratatui::{style::Color, symbols::border, text::Line};
+use serde::Deserialize;
+
+use crate::{
+    capabilities::capabilities,
+    error::{io, Error, Result},
+};
+
+const PALETTE_FILE: &str = "palette.json";
+const BORDERS_FILE: &str = "borders.json";
+const FONTS_DIR: &str = "fonts";
+
+/// FIGlet font, glyph rows of printable ASCII characters.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FigletFont {
+    height: usize,
+    glyphs: HashMap<char, Vec<String>>,
+}
+
+impl FigletFont {
+    /// Parse `.flf` font, only the required ASCII characters are loaded.
+    pub fn parse(source: &str) -> Result<Self> {
+        let mut lines = source.lines();
+        let header = lines.next().unwrap_or_default();
+        let invalid = |reason: &str| Error::Parse(format!("invalid FIGlet font: {}", reason));
+        let Some(params) = header.strip_prefix("flf2a") else {
+            return Err(invalid("missing flf2a header"));
+        };
+        let hardblank = params
+            .chars()
+            .next()
+            .ok_or_else(|| invalid("missing hardblank"))?;
+        let numbers: Vec<usize> = params[hardblank.len_utf8()..]
+            .split_whitespace()
+            .map(|n| n.parse().unwrap_or(0))
+            .collect();
+        let (height, comment_lines) = match numbers.as_slice() {
+            [height, _, _, _, comments, ..] if *height > 0 => (*height, *comments),
+            _ => return Err(invalid("bad header values")),
+        };
+        let mut lines = lines.skip(comment_lines);
+
+        let mut glyphs = HashMap::new();
+        for c in ' '..='~' {
+            let mut rows = vec![];
+            for _ in 0..height {
+                let line = lines
+                    .next()
+                    .ok_or_else(|| invalid(&format!("glyph '{}' is incomplete", c)))?;
+                let end = line.chars().last().unwrap_or('@');
+                let row = line.trim_end_matches(end).replace(hardblank, " ");
+                rows.push(row);
+            }
+            glyphs.insert(c, rows);
+        }
+        Ok(Self { height, glyphs })
+    }
+
+    /// Text drawn with the font, glyphs are put side by side without smushing.
+    pub fn render(&self, text: &str) -> Vec<String> {
+        let mut rows = vec![];
+        for text_line in text.split('\n') {
+            for row in 0..self.height {
+                let line: String = text_line
+                    .chars()
+                    .filter_map(|c| self.glyphs.get(&c))
+                    .map(|glyph| glyph[row].as_str())
+                    .collect();
+                rows.push(line);
+            }
+        }
+        rows
+    }
+}
+
+/// Border glyphs as written in `borders.json`, each has to be single cell wide.
+#[derive(Debug, Deserialize)]
+struct BorderGlyphs {
+    top_left: String,
+    top_right: String,
+    bottom_left: String,
+    bottom_right: String,
+    vertical_left: String,
+    vertical_right: String,
+    horizontal_top: String,
+    horizontal_bottom: String,
+}
+
+fn glyph(name: &str, value: String) -> Result<&'static str> {
+    let width = Line::from(value.as_str()).width();
+    if width != 1 {
+        return Err(Error::Parse(format!(
+            "border glyph '{}' has to be one cell wide, got '{}'",
+            name, value
+        )));
+    }
+    Ok(intern(value))
+}
+
+/// Border set needs static strings, each distinct glyph is leaked only once so
+/// reloading the deck does not leak again.
+fn intern(value: String) -> &'static str {
+    static GLYPHS: OnceLock<Mutex<HashSet<&'static str>>> = OnceLock::new();
+    let mut glyphs = GLYPHS
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    if let Some(glyph) = glyphs.get(value.as_str()) {
+        return glyph;
+    }
+    let glyph: &'static str = Box::leak(value.into_boxed_str());
+    glyphs.insert(glyph);
+    glyph
+}
+
+impl BorderGlyphs {
+    fn into_set(self) -> Result<border::Set> {
+        Ok(border::Set {
+            top_left: glyph("top_left", self.top_left)?,
+            top_right: glyph("top_right", self.top_right)?,
+            bottom_left: glyph("bottom_left", self.bottom_left)?,
+            bottom_right: glyph("bottom_right", self.bottom_right)?,
+            vertical_left: glyph("vertical_left", self.vertical_left)?,
+            vertical_right: glyph("vertical_right", self.vertical_right)?,
+            horizontal_top: glyph("horizontal_top", self.horizontal_top)?,
+            horizontal_bottom: glyph("horizontal_bottom", self.horizontal_bottom)?,
+        })
+    }
+}
+
+/// Assets of deck theme directory: named colors, border glyphs and FIGlet fonts.
+#[derive(Debug, Clone, Default)]
+pub struct Theme {
+    pub palette: HashMap<String, Color>,
+    pub border: Option<border::Set>,
+    pub fonts: HashMap<String, FigletFont>,
+}
+
+fn read_json<T: for<'de> Deserialize<'de>>(path: &Path) -> Result<Option<T>> {
+    if !path.exists() {
+        return Ok(None);
+    }
+    let content = std::fs::read_to_string(path).map_err(io(format!(
+        "failed to read theme file '{}'",
+        path.display()
+    )))?;
+    serde_json::from_str(&content)
+        .map(Some)
+        .map_err(|e| Error::Parse(format!("'{}': {}", path.display(), e)))
+}
+
+impl Theme {
+    /// Load and validate theme directory, every file in it is optional.
+    pub fn load(dir: &Path) -> Result<Self> {
+        if !dir.is_dir() {
+            return Err(Error::Parse(format!(
+                "theme directory '{}' not found",
+                dir.display()
+            )));
+        }
+        let mut theme = Self::default();
+
+        let palette: HashMap<String, String> =
+            read_json(&dir.join(PALETTE_FILE))?.unwrap_or_default();
+        for (name, value) in palette {
+            let color = Color::from_str(&value).map_err(|_| {
+                Error::Parse(format!(
+                    "palette color '{}' has invalid value '{}'",
+                    name, value
+                ))
+            })?;
+            theme.palette.insert(name, color);
+        }
+
+        if let Some(borders) = read_json::<BorderGlyphs>(&dir.join(BORDERS_FILE))? {
+            theme.border = Some(borders.into_set()?);
+        }
+
+        let fonts_dir = dir.join(FONTS_DIR);
+        if fonts_dir.is_dir() {
+            let entries = std::fs::read_dir(&fonts_dir)
+                .map_err(io(format!("failed to read '{}'", fonts_dir.display())))?;
+            for path in entries.flatten().map(|e| e.path()) {
+                if path.extension().and_then(|e| e.to_str()) != Some("flf") {
+                    continue;
+                }
+                let Some(name) = path.file_stem().and_then(|s| s.to_str()) else {
+                    continue;
+                };
+                let source = std::fs::read_to_string(&path)
+                    .map_err(io(format!("failed to read font '{}'", path.display())))?;
+                let font = FigletFont::parse(&source).map_err(|e| match e {
+                    Error::Parse(e) => Error::Parse(format!("'{}': {}", path.display(), e)),
+                    e => e,
+                })?;
+                theme.fonts.insert(name.to_string(), font);
+            }
+        }
+        Ok(theme)
+    }
+}
+
+static THEME: RwLock<Option<Arc<Theme>>> = RwLock::new(None);
+
+/// Theme of the loaded deck, default when the deck has none.
+pub fn theme() -> Arc<Theme> {
+    THEME
+        .read()
+        .ok()
+        .and_then(|t| t.clone())
+        .unwrap_or_default()
+}
+
+pub fn set_theme(theme: Theme) {
+    if let Ok(mut current) = THEME.write() {
+        *current = Some(Arc::new(theme));
+    }
+}
+
+/// Color from slides, palette names of the theme first, then `#RRGGBB` or name.
+pub fn parse_color(color: &str) -> Option<Color> {
+    let named = theme().palette.get(color).copied();
+    let caps = capabilities();
+    named
+        .map(|c| caps.color(c))
+        .or_else(|| caps.parse_color(color))
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_glyphs_are_interned() {
+        let first = glyph("top_left", "╔".to_string()).unwrap();
+        let second = glyph("top_left", "╔".to_string()).unwrap();
+        assert_eq!(first.as_ptr(), second.as_ptr());
+        assert!(glyph("top_left", "ab".to_string()).is_err());
+    }
+
+    #[test]
+    fn test_figlet_font() {
+        let mut source = String::from("flf2a$ 2 2 4 0 1\ncomment\n");
+        for c in ' '..='~' {
+            match c {
+                'H' => source.push_str("|_|@\n| |@@\n"),
+                'i' => source.push_str("o@\n|@@\n"),
+                _ => source.push_str("$@\n$@@\n"),
+            }
+        }
+        let font = FigletFont::parse(&source).unwrap();
+        assert_eq!(font.render("Hi "), vec!["|_|o ", "| || "]);
+        assert!(FigletFont::parse("flf2a$ 2 2 4 0 0\n@\n").is_err());
+    }
+}
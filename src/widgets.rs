@@ -0,0 +1,20 @@
+pub mod annotation;
+pub mod clock;
+pub mod countdown;
+pub mod help;
+pub mod icon;
+pub mod menu;
+pub mod miniature;
+pub mod nav_hud;
+pub mod overview;
+pub mod palette;
+pub mod pie;
+pub mod plot;
+pub mod pointer;
+pub mod progress;
+pub mod prompt;
+pub mod qr;
+pub mod questions;
+pub mod rule;
+pub mod terminal;
+pub mod toc;
@@ -0,0 +1,152 @@
+use std::collections::HashMap;
+
+use ratatui::prelude::*;
+
+/// Colors `D` cycles through in drawing mode.
+pub const DRAW_COLORS: [Color; 5] = [
+    Color::LightRed,
+    Color::Yellow,
+    Color::LightGreen,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Braille dots of cell are 2 wide and 4 high.
+const DOTS_X: u16 = 2;
+const DOTS_Y: u16 = 4;
+
+/// Line drawn with one mouse drag, points are braille dots of the terminal.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Stroke {
+    pub color: Color,
+    pub dots: Vec<(u16, u16)>,
+}
+
+/// Freehand drawing over slide, strokes are undone last first.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Annotation {
+    strokes: Vec<Stroke>,
+}
+
+/// Dot in the middle of terminal cell.
+fn cell_dot(column: u16, row: u16) -> (u16, u16) {
+    (column * DOTS_X, row * DOTS_Y + 1)
+}
+
+impl Annotation {
+    pub fn is_empty(&self) -> bool {
+        self.strokes.is_empty()
+    }
+
+    /// New stroke at cell pressed.
+    pub fn begin(&mut self, color: Color, column: u16, row: u16) {
+        self.strokes.push(Stroke {
+            color,
+            dots: vec![cell_dot(column, row)],
+        });
+    }
+
+    /// Stroke continues to cell dragged to, dots between are filled so fast drags
+    /// leave no gaps.
+    pub fn extend(&mut self, column: u16, row: u16) {
+        let Some(stroke) = self.strokes.last_mut() else {
+            return;
+        };
+        let to = cell_dot(column, row);
+        let from = stroke.dots.last().copied().unwrap_or(to);
+        let steps = from.0.abs_diff(to.0).max(from.1.abs_diff(to.1));
+        for step in 1..=steps {
+            let along = |a: u16, b: u16| {
+                (a as i32 + (b as i32 - a as i32) * step as i32 / steps as i32) as u16
+            };
+            stroke.dots.push((along(from.0, to.0), along(from.1, to.1)));
+        }
+    }
+
+    pub fn undo(&mut self) {
+        self.strokes.pop();
+    }
+
+    pub fn clear(&mut self) {
+        self.strokes.clear();
+    }
+}
+
+/// Braille bit of dot at `x`, `y` inside its cell.
+fn dot_bit(x: u16, y: u16) -> u8 {
+    match (x, y) {
+        (0, 3) => 0x40,
+        (1, 3) => 0x80,
+        (0, y) => 1 << y,
+        (_, y) => 1 << (y + 3),
+    }
+}
+
+/// Cells of annotation with braille dots and color of the last stroke over them.
+pub fn annotation_cells(annotation: &Annotation) -> HashMap<(u16, u16), (u8, Color)> {
+    let mut cells: HashMap<(u16, u16), (u8, Color)> = HashMap::new();
+    for stroke in &annotation.strokes {
+        for &(x, y) in &stroke.dots {
+            let cell = cells
+                .entry((x / DOTS_X, y / DOTS_Y))
+                .or_insert((0, stroke.color));
+            cell.0 |= dot_bit(x % DOTS_X, y % DOTS_Y);
+            cell.1 = stroke.color;
+        }
+    }
+    cells
+}
+
+/// Annotation drawn on top of the slide, cells keep their background.
+pub struct AnnotationLayer<'a> {
+    annotation: &'a Annotation,
+}
+
+impl<'a> AnnotationLayer<'a> {
+    pub fn new(annotation: &'a Annotation) -> Self {
+        Self { annotation }
+    }
+}
+
+impl Widget for AnnotationLayer<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        for ((x, y), (bits, color)) in annotation_cells(self.annotation) {
+            if !area.contains(layout::Position::new(x, y)) {
+                continue;
+            }
+            let symbol = char::from_u32(0x2800 + bits as u32).unwrap_or(' ');
+            buf.get_mut(x, y)
+                .set_symbol(&symbol.to_string())
+                .set_fg(color);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_annotation() {
+        let mut annotation = Annotation::default();
+        annotation.begin(Color::Red, 1, 0);
+        annotation.extend(3, 0);
+        let cells = annotation_cells(&annotation);
+        assert_eq!(cells.len(), 3);
+        // -- horizontal line through the second dot row of each cell
+        assert_eq!(cells[&(1, 0)], (0x02 | 0x10, Color::Red));
+        assert_eq!(cells[&(3, 0)], (0x02, Color::Red));
+
+        annotation.begin(Color::Yellow, 3, 0);
+        assert_eq!(
+            annotation_cells(&annotation)[&(3, 0)],
+            (0x02, Color::Yellow)
+        );
+        annotation.undo();
+        assert_eq!(annotation_cells(&annotation)[&(3, 0)], (0x02, Color::Red));
+        annotation.clear();
+        assert!(annotation.is_empty());
+    }
+}
@@ -0,0 +1,54 @@
+use std::fmt::Write;
+
+use ratatui::prelude::*;
+use tui_big_text::{BigText, PixelSize};
+
+/// Current local time, drawn big when `big` is set and there is space for it.
+pub struct ClockWidget {
+    format: String,
+    color: Color,
+    big: bool,
+}
+
+impl ClockWidget {
+    pub fn new(format: Option<String>, color: Color) -> Self {
+        Self {
+            format: format.unwrap_or_else(|| String::from("%H:%M")),
+            color,
+            big: false,
+        }
+    }
+
+    pub fn big(mut self, big: bool) -> Self {
+        self.big = big;
+        self
+    }
+}
+
+impl Widget for ClockWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let mut text = String::new();
+        if write!(text, "{}", chrono::Local::now().format(&self.format)).is_err() {
+            Line::from(format!("invalid clock format '{}'", self.format))
+                .red()
+                .render(area, buf);
+            return;
+        }
+        let style = Style::default().fg(self.color);
+        if self.big && area.height >= 4 {
+            let big_text = BigText::builder()
+                .pixel_size(PixelSize::Quadrant)
+                .style(style)
+                .lines(vec![text.clone().into()])
+                .alignment(Alignment::Center)
+                .build();
+            if let Ok(big_text) = big_text {
+                big_text.render(area, buf);
+                return;
+            }
+        }
+        Line::styled(text, style)
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
@@ -0,0 +1,120 @@
+use std::time::Duration;
+
+use ratatui::prelude::*;
+use tui_big_text::{BigText, PixelSize};
+
+/// Remaining time of `duration` counted from slide entry, drawn big when there is
+/// space for it. Turns yellow in the last fifth and red in the last tenth.
+pub struct CountdownWidget {
+    duration: Duration,
+    elapsed: Duration,
+    color: Color,
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    if secs >= 3600 {
+        format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)
+    } else {
+        format!("{:02}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+/// Time left of talk of `duration` for the footer, yellow in the last 5 minutes, red in
+/// the last one and with minus sign in overtime.
+pub fn talk_remaining(duration: Duration, elapsed: Duration) -> (String, Color) {
+    if elapsed > duration {
+        return (
+            format!("-{}", format_duration(elapsed - duration)),
+            Color::Red,
+        );
+    }
+    let remaining = duration - elapsed;
+    let color = match remaining.as_secs() {
+        0..=60 => Color::Red,
+        61..=300 => Color::Yellow,
+        _ => Color::DarkGray,
+    };
+    (format_duration(remaining), color)
+}
+
+impl CountdownWidget {
+    pub fn new(duration: Duration, color: Color) -> Self {
+        Self {
+            duration,
+            elapsed: Duration::ZERO,
+            color,
+        }
+    }
+
+    pub fn elapsed(mut self, elapsed: Duration) -> Self {
+        self.elapsed = elapsed;
+        self
+    }
+
+    fn color(&self, remaining: Duration) -> Color {
+        if remaining <= self.duration / 10 {
+            Color::Red
+        } else if remaining <= self.duration / 5 {
+            Color::Yellow
+        } else {
+            self.color
+        }
+    }
+}
+
+impl Widget for CountdownWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        // -- round up so the last second shows 00:01 and not 00:00
+        let remaining = self
+            .duration
+            .saturating_sub(self.elapsed)
+            .saturating_add(Duration::from_millis(999))
+            .min(self.duration);
+        let text = format_duration(remaining);
+        let style = Style::default().fg(self.color(remaining));
+        if area.height >= 4 {
+            let big_text = BigText::builder()
+                .pixel_size(PixelSize::Quadrant)
+                .style(style)
+                .lines(vec![text.clone().into()])
+                .alignment(Alignment::Center)
+                .build();
+            if let Ok(big_text) = big_text {
+                big_text.render(area, buf);
+                return;
+            }
+        }
+        Line::styled(text, style)
+            .alignment(Alignment::Center)
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_talk_remaining() {
+        let minutes = |m: u64| Duration::from_secs(m * 60);
+        assert_eq!(
+            talk_remaining(minutes(30), minutes(10)),
+            ("20:00".to_string(), Color::DarkGray)
+        );
+        assert_eq!(
+            talk_remaining(minutes(30), minutes(26)),
+            ("04:00".to_string(), Color::Yellow)
+        );
+        assert_eq!(
+            talk_remaining(minutes(30), minutes(29) + Duration::from_secs(30)),
+            ("00:30".to_string(), Color::Red)
+        );
+        assert_eq!(
+            talk_remaining(minutes(30), minutes(32)),
+            ("-02:00".to_string(), Color::Red)
+        );
+    }
+}
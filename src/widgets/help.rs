@@ -0,0 +1,105 @@
+use std::collections::BTreeMap;
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+use crate::{action::Action, config::key_event_to_string};
+
+const HELP_WIDTH: u16 = 60;
+
+/// Actions of key bindings with their keys, like `("Next", "<pagedown> <right>")`,
+/// sorted by action.
+pub fn key_help<'a>(
+    bindings: impl IntoIterator<Item = (&'a Vec<KeyEvent>, &'a Action)>,
+) -> Vec<(String, String)> {
+    let mut actions: BTreeMap<String, Vec<String>> = BTreeMap::new();
+    for (keys, action) in bindings {
+        let keys: Vec<String> = keys.iter().map(key_event_to_string).collect();
+        actions
+            .entry(format!("{action:?}"))
+            .or_default()
+            .push(format!("<{}>", keys.join("><")));
+    }
+    actions
+        .into_iter()
+        .map(|(action, mut keys)| {
+            keys.sort();
+            (action, keys.join(" "))
+        })
+        .collect()
+}
+
+/// Popup in the middle of the area listing bound keys.
+pub struct Help<'a> {
+    actions: &'a [(String, String)],
+}
+
+impl<'a> Help<'a> {
+    pub fn new(actions: &'a [(String, String)]) -> Self {
+        Self { actions }
+    }
+}
+
+impl Widget for Help<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = HELP_WIDTH.min(area.width);
+        let height = (self.actions.len() as u16 + 2).min(area.height);
+        let rect = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + (area.height - height) / 2,
+            width,
+            height,
+        );
+        Clear.render(rect, buf);
+        let action_width = self.actions.iter().map(|(a, _)| a.len()).max().unwrap_or(0);
+        let lines: Vec<Line> = self
+            .actions
+            .iter()
+            .map(|(action, keys)| {
+                Line::from(vec![
+                    format!("{action:<action_width$}  ").yellow(),
+                    keys.clone().into(),
+                ])
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Keys, ? to close ");
+        Paragraph::new(lines).block(block).render(rect, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crossterm::event::{KeyCode, KeyModifiers};
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_key_help() {
+        let key = |code| KeyEvent::new(code, KeyModifiers::NONE);
+        let bindings = [
+            (vec![key(KeyCode::Right)], Action::Next),
+            (vec![key(KeyCode::PageDown)], Action::Next),
+            (
+                vec![key(KeyCode::Char('g')), key(KeyCode::Char('g'))],
+                Action::First,
+            ),
+            (vec![key(KeyCode::Char('1'))], Action::GotoSlide(3)),
+        ];
+        assert_eq!(
+            key_help(bindings.iter().map(|(k, a)| (k, a))),
+            vec![
+                ("First".to_string(), "<g><g>".to_string()),
+                ("GotoSlide(3)".to_string(), "<1>".to_string()),
+                ("Next".to_string(), "<pagedown> <right>".to_string()),
+            ]
+        );
+    }
+}
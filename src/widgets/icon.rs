@@ -0,0 +1,84 @@
+use ratatui::prelude::*;
+
+use crate::capabilities::{capabilities, UnicodeLevel};
+
+/// Nerd Font glyph for icon name.
+pub fn icon_glyph(name: &str) -> Option<char> {
+    let code = match name.to_lowercase().as_str() {
+        "github" => 0xf09b,
+        "gitlab" => 0xf296,
+        "git" => 0xe702,
+        "rust" => 0xe7a8,
+        "linux" => 0xf17c,
+        "apple" => 0xf179,
+        "windows" => 0xf17a,
+        "ubuntu" => 0xf31b,
+        "arch" => 0xf303,
+        "debian" => 0xf306,
+        "fedora" => 0xf30a,
+        "nixos" => 0xf313,
+        "docker" => 0xe7b0,
+        "python" => 0xe73c,
+        "javascript" => 0xe74e,
+        "go" => 0xe724,
+        "vim" => 0xe7c5,
+        "terminal" => 0xf120,
+        "bitcoin" => 0xf15a,
+        "twitter" => 0xf099,
+        "youtube" => 0xf16a,
+        "mail" => 0xf0e0,
+        "heart" => 0xf004,
+        "star" => 0xf005,
+        "check" => 0xf00c,
+        "warning" => 0xf071,
+        "info" => 0xf05a,
+        "lock" => 0xf023,
+        "cloud" => 0xf0c2,
+        "database" => 0xf1c0,
+        "book" => 0xf02d,
+        "clock" => 0xf017,
+        _ => return None,
+    };
+    char::from_u32(code)
+}
+
+/// Nerd Font icon centered in its area.
+pub struct IconWidget {
+    name: String,
+    color: Color,
+}
+
+impl IconWidget {
+    pub fn new(name: &str, color: Color) -> Self {
+        Self {
+            name: name.to_string(),
+            color,
+        }
+    }
+}
+
+impl Widget for IconWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.is_empty() {
+            return;
+        }
+        let caps = capabilities();
+        // -- name itself can be a glyph
+        let glyph = icon_glyph(&self.name).or_else(|| {
+            let mut chars = self.name.chars();
+            chars.next().filter(|_| chars.next().is_none())
+        });
+        let (Some(glyph), true) = (glyph, caps.unicode == UnicodeLevel::Full) else {
+            Line::from(format!("[{}]", self.name))
+                .style(Style::default().fg(self.color))
+                .alignment(Alignment::Center)
+                .render(area, buf);
+            return;
+        };
+
+        let x = area.x + (area.width - 1) / 2;
+        let y = area.y + (area.height - 1) / 2;
+        buf.get_mut(x, y).set_char(glyph).set_fg(self.color);
+    }
+}
@@ -0,0 +1,34 @@
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState},
+};
+
+use crate::enums::MenuOptionJson;
+
+/// Options of `Menu` item, the selected one is followed with Enter.
+pub struct MenuWidget<'a> {
+    options: &'a [MenuOptionJson],
+    selected: usize,
+}
+
+impl<'a> MenuWidget<'a> {
+    pub fn new(options: &'a [MenuOptionJson], selected: usize) -> Self {
+        Self { options, selected }
+    }
+}
+
+impl Widget for MenuWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let items: Vec<ListItem> = self
+            .options
+            .iter()
+            .enumerate()
+            .map(|(i, option)| ListItem::new(format!(" {}. {} ", i + 1, option.label)))
+            .collect();
+        let list = List::new(items)
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow).bold())
+            .highlight_symbol("› ");
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
@@ -0,0 +1,76 @@
+use ratatui::prelude::*;
+
+/// Slide drawn into `source` buffer shrunk to fit the area. Each cell shows the first
+/// non-blank cell of the part of the slide it covers, so layout and colors stay
+/// recognizable while text mostly does not.
+pub struct Miniature<'a> {
+    source: &'a Buffer,
+}
+
+impl<'a> Miniature<'a> {
+    pub fn new(source: &'a Buffer) -> Self {
+        Self { source }
+    }
+}
+
+/// Part of `source` covered by cell `offset` of `target` cells.
+fn covered(offset: u16, target: u16, source: u16) -> (u16, u16) {
+    let start = (offset as u32 * source as u32 / target as u32) as u16;
+    let end = ((offset as u32 + 1) * source as u32 / target as u32) as u16;
+    (start, end.max(start + 1).min(source))
+}
+
+/// Smallest area with the aspect of `source` filling `width` or `height`.
+pub fn miniature_size(source: Rect, width: u16, height: u16) -> (u16, u16) {
+    if source.width == 0 || source.height == 0 {
+        return (0, 0);
+    }
+    let fit_height = (width as u32 * source.height as u32 / source.width as u32) as u16;
+    if fit_height <= height {
+        (width, fit_height.max(1))
+    } else {
+        let fit_width = (height as u32 * source.width as u32 / source.height as u32) as u16;
+        (fit_width.max(1), height)
+    }
+}
+
+impl Widget for Miniature<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let source = self.source.area;
+        if source.width == 0 || source.height == 0 {
+            return;
+        }
+        for y in 0..area.height {
+            let (top, bottom) = covered(y, area.height, source.height);
+            for x in 0..area.width {
+                let (left, right) = covered(x, area.width, source.width);
+                let mut cells = (top..bottom)
+                    .flat_map(|sy| (left..right).map(move |sx| (source.x + sx, source.y + sy)))
+                    .map(|(sx, sy)| self.source.get(sx, sy));
+                let background = self.source.get(source.x + left, source.y + top);
+                let picked = cells
+                    .find(|cell| !cell.symbol().trim().is_empty())
+                    .unwrap_or(background);
+                *buf.get_mut(area.x + x, area.y + y) = picked.clone();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_miniature() {
+        let source = Buffer::with_lines(vec!["ab  ", "    ", "  cd", "    "]);
+        let mut buf = Buffer::empty(Rect::new(0, 0, 2, 2));
+        Miniature::new(&source).render(buf.area, &mut buf);
+        assert_eq!(buf, Buffer::with_lines(vec!["a ", " c"]));
+
+        assert_eq!(miniature_size(Rect::new(0, 0, 80, 20), 40, 40), (40, 10));
+        assert_eq!(miniature_size(Rect::new(0, 0, 80, 20), 40, 5), (20, 5));
+    }
+}
@@ -0,0 +1,107 @@
+use ratatui::prelude::*;
+
+use crate::capabilities::{capabilities, UnicodeLevel};
+
+/// Rows taken by the strip at the bottom of the screen, title and markers.
+pub const NAV_HUD_HEIGHT: u16 = 2;
+
+/// Slide under column `x` of strip `width` cells wide split evenly between `count`
+/// slides.
+pub fn marker_at(x: u16, width: u16, count: usize) -> Option<usize> {
+    if width == 0 || count == 0 || x >= width {
+        return None;
+    }
+    Some((x as usize * count / width as usize).min(count - 1))
+}
+
+/// Strip of slide markers with title of hovered slide, like seek bar of video player.
+pub struct NavHud<'a> {
+    titles: &'a [Option<String>],
+    current: usize,
+    hovered: Option<usize>,
+}
+
+impl<'a> NavHud<'a> {
+    pub fn new(titles: &'a [Option<String>], current: usize) -> Self {
+        Self {
+            titles,
+            current,
+            hovered: None,
+        }
+    }
+
+    pub fn hovered(mut self, hovered: Option<usize>) -> Self {
+        self.hovered = hovered;
+        self
+    }
+}
+
+impl Widget for NavHud<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let count = self.titles.len();
+        if area.height < NAV_HUD_HEIGHT || count == 0 {
+            return;
+        }
+        let caps = capabilities();
+        let markers_y = area.bottom() - 1;
+        let bar = caps.glyph(UnicodeLevel::Basic, "━", "=");
+        let gap = caps.glyph(UnicodeLevel::Basic, "─", "-");
+        for x in 0..area.width {
+            let Some(index) = marker_at(x, area.width, count) else {
+                continue;
+            };
+            let color = if Some(index) == self.hovered {
+                Color::Yellow
+            } else if index == self.current {
+                Color::Green
+            } else {
+                Color::Rgb(100, 100, 100)
+            };
+            let first = marker_at(x.saturating_sub(1), area.width, count) != Some(index);
+            let symbol = if first && x > 0 { gap } else { bar };
+            buf.get_mut(area.x + x, markers_y)
+                .set_symbol(symbol)
+                .set_style(Style::default().fg(color).bg(Color::Black));
+        }
+
+        let title_y = markers_y - 1;
+        buf.set_style(
+            Rect::new(area.x, title_y, area.width, 1),
+            Style::default().bg(Color::Black),
+        );
+        let Some(hovered) = self.hovered else {
+            return;
+        };
+        let title = self.titles[hovered].as_deref().unwrap_or_default();
+        let label = format!(" {}/{} {} ", hovered + 1, count, title);
+        let width = (label.chars().count() as u16).min(area.width);
+        let center = area.x as usize + (hovered * 2 + 1) * area.width as usize / (count * 2);
+        let x = (center as u16)
+            .saturating_sub(width / 2)
+            .clamp(area.x, area.right() - width);
+        buf.set_stringn(
+            x,
+            title_y,
+            label,
+            width as usize,
+            Style::default().fg(Color::Black).bg(Color::Yellow),
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_marker_at() {
+        assert_eq!(marker_at(0, 80, 4), Some(0));
+        assert_eq!(marker_at(39, 80, 4), Some(1));
+        assert_eq!(marker_at(79, 80, 4), Some(3));
+        assert_eq!(marker_at(80, 80, 4), None);
+        assert_eq!(marker_at(5, 10, 100), Some(50));
+        assert_eq!(marker_at(0, 80, 0), None);
+    }
+}
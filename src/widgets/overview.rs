@@ -0,0 +1,97 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+};
+
+/// Size of one slide preview in the grid, borders included.
+pub const CELL_WIDTH: u16 = 26;
+pub const CELL_HEIGHT: u16 = 7;
+
+/// Preview of slide in overview grid, its title and text of the first item.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SlidePreview {
+    pub title: Option<String>,
+    pub text: String,
+}
+
+/// Number of previews in grid row of area `width` cells wide.
+pub fn grid_columns(width: u16) -> usize {
+    (width / CELL_WIDTH).max(1) as usize
+}
+
+/// First grid row drawn, so that row of `selected` preview is one of `rows` shown.
+pub fn first_row(selected: usize, columns: usize, rows: usize) -> usize {
+    let row = selected / columns.max(1);
+    row.saturating_sub(rows.max(1) - 1)
+}
+
+/// Grid of slide previews to pick slide from, like the slide sorter of editors.
+pub struct Overview<'a> {
+    previews: &'a [SlidePreview],
+    selected: usize,
+    current: usize,
+}
+
+impl<'a> Overview<'a> {
+    pub fn new(previews: &'a [SlidePreview], selected: usize, current: usize) -> Self {
+        Self {
+            previews,
+            selected,
+            current,
+        }
+    }
+}
+
+impl Widget for Overview<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let columns = grid_columns(area.width);
+        let rows = (area.height / CELL_HEIGHT).max(1) as usize;
+        let first = first_row(self.selected, columns, rows) * columns;
+        let x_offset = (area.width.saturating_sub(columns as u16 * CELL_WIDTH)) / 2;
+        let shown = self.previews.iter().enumerate().skip(first);
+        for (i, (index, preview)) in shown.take(columns * rows).enumerate() {
+            let rect = Rect::new(
+                area.x + x_offset + (i % columns) as u16 * CELL_WIDTH,
+                area.y + (i / columns) as u16 * CELL_HEIGHT,
+                CELL_WIDTH,
+                CELL_HEIGHT,
+            )
+            .intersection(area);
+            let border = if index == self.selected {
+                Color::Yellow
+            } else if index == self.current {
+                Color::Green
+            } else {
+                Color::Rgb(100, 100, 100)
+            };
+            let title = preview.title.as_deref().unwrap_or_default();
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(Style::default().fg(border))
+                .title(format!(" {} {} ", index + 1, title));
+            Paragraph::new(preview.text.as_str())
+                .dark_gray()
+                .wrap(Wrap { trim: true })
+                .block(block)
+                .render(rect, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_grid_rows() {
+        assert_eq!(grid_columns(80), 3);
+        assert_eq!(grid_columns(10), 1);
+        assert_eq!(first_row(4, 3, 2), 0);
+        assert_eq!(first_row(7, 3, 2), 1);
+        assert_eq!(first_row(59, 3, 3), 17);
+    }
+}
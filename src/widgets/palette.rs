@@ -0,0 +1,79 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+};
+
+const PALETTE_WIDTH: u16 = 50;
+/// Matches listed under the query line.
+const PALETTE_ROWS: u16 = 10;
+
+/// Popup at the top of the area with typed query and slide titles matching it, like
+/// the command palette of editors.
+pub struct Palette<'a> {
+    query: &'a str,
+    /// Slide index and title of each match, best first.
+    matches: &'a [(usize, String)],
+    selected: usize,
+}
+
+impl<'a> Palette<'a> {
+    pub fn new(query: &'a str, matches: &'a [(usize, String)], selected: usize) -> Self {
+        Self {
+            query,
+            matches,
+            selected,
+        }
+    }
+}
+
+impl Widget for Palette<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let width = PALETTE_WIDTH.min(area.width);
+        let height = (PALETTE_ROWS + 3).min(area.height);
+        let rect = Rect::new(
+            area.x + (area.width - width) / 2,
+            area.y + area.height / 6,
+            width,
+            height,
+        )
+        .intersection(area);
+        Clear.render(rect, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(" Go to slide ");
+        let inner = block.inner(rect);
+        block.render(rect, buf);
+        if inner.height == 0 {
+            return;
+        }
+        let query = Line::from(vec![
+            "> ".yellow(),
+            Span::raw(self.query),
+            Span::styled(" ", Style::default().bg(Color::White)),
+        ]);
+        buf.set_line(inner.x, inner.y, &query, inner.width);
+
+        let items: Vec<ListItem> = self
+            .matches
+            .iter()
+            .map(|(index, title)| {
+                ListItem::new(Line::from(vec![
+                    format!("{:>3} ", index + 1).dark_gray(),
+                    title.clone().into(),
+                ]))
+            })
+            .collect();
+        let list =
+            List::new(items).highlight_style(Style::default().fg(Color::Black).bg(Color::Yellow));
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        let list_rect = Rect::new(
+            inner.x,
+            inner.y + 1,
+            inner.width,
+            inner.height.saturating_sub(1),
+        );
+        StatefulWidget::render(list, list_rect, buf, &mut state);
+    }
+}
@@ -0,0 +1,216 @@
+use std::f64::consts::PI;
+
+use ratatui::{prelude::*, widgets::Paragraph};
+
+use crate::{
+    capabilities::{capabilities, UnicodeLevel},
+    locale::locale,
+};
+
+const DEFAULT_COLORS: [Color; 6] = [
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+];
+
+const LEGEND_MIN_CHART_WIDTH: u16 = 10;
+const DONUT_RATIO: f64 = 0.5;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct PieSlice {
+    pub label: String,
+    pub value: f64,
+    pub color: Color,
+}
+
+impl PieSlice {
+    pub fn new(label: String, value: f64, color: Option<Color>, index: usize) -> Self {
+        Self {
+            label,
+            value,
+            color: color.unwrap_or(DEFAULT_COLORS[index % DEFAULT_COLORS.len()]),
+        }
+    }
+}
+
+/// Pie (or donut) chart drawn with braille dots, with a legend on the right side.
+#[derive(Debug, Clone, Default)]
+pub struct PieChart {
+    slices: Vec<PieSlice>,
+    donut: bool,
+}
+
+impl PieChart {
+    pub fn new(slices: Vec<PieSlice>) -> Self {
+        Self {
+            slices,
+            donut: false,
+        }
+    }
+
+    pub fn donut(mut self, donut: bool) -> Self {
+        self.donut = donut;
+        self
+    }
+
+    fn total(&self) -> f64 {
+        self.slices.iter().map(|s| s.value.max(0.0)).sum()
+    }
+
+    /// Index of the slice covering `angle` (radians clockwise from 12 o'clock).
+    fn slice_at(&self, angle: f64, total: f64) -> Option<usize> {
+        let mut acc = 0.0;
+        for (i, slice) in self.slices.iter().enumerate() {
+            acc += slice.value.max(0.0) / total * 2.0 * PI;
+            if angle < acc {
+                return Some(i);
+            }
+        }
+        self.slices.len().checked_sub(1)
+    }
+
+    fn legend_lines(&self, total: f64) -> Vec<Line<'static>> {
+        let marker = capabilities().glyph(UnicodeLevel::Basic, "■ ", "# ");
+        self.slices
+            .iter()
+            .map(|s| {
+                let percent = s.value.max(0.0) / total * 100.0;
+                let percent = match locale() {
+                    Some(locale) => locale.number(percent, 1),
+                    None => format!("{:.1}", percent),
+                };
+                Line::from(vec![
+                    Span::styled(marker, Style::default().fg(s.color)),
+                    Span::raw(format!("{} {}%", s.label, percent)),
+                ])
+            })
+            .collect()
+    }
+
+    fn render_chart(&self, area: Rect, buf: &mut Buffer, total: f64) {
+        // -- braille cell is 2x4 dots, terminal cell has roughly 1:2 ratio so dots are square
+        let dots_w = area.width as f64 * 2.0;
+        let dots_h = area.height as f64 * 4.0;
+        let radius = dots_w.min(dots_h) / 2.0;
+        let inner = if self.donut {
+            radius * DONUT_RATIO
+        } else {
+            0.0
+        };
+        let (cx, cy) = (dots_w / 2.0, dots_h / 2.0);
+        const BITS: [[u32; 4]; 2] = [[0x01, 0x02, 0x04, 0x40], [0x08, 0x10, 0x20, 0x80]];
+        // -- without braille whole cells are filled once at least half of the dots are set
+        let unicode = capabilities().unicode;
+        let fill = if unicode >= UnicodeLevel::Basic {
+            '█'
+        } else {
+            '#'
+        };
+
+        for row in 0..area.height {
+            for col in 0..area.width {
+                let mut bits = 0;
+                let mut counts = vec![0; self.slices.len()];
+                for (dx, column_bits) in BITS.iter().enumerate() {
+                    for (dy, bit) in column_bits.iter().enumerate() {
+                        let px = (col as usize * 2 + dx) as f64 + 0.5 - cx;
+                        let py = (row as usize * 4 + dy) as f64 + 0.5 - cy;
+                        let dist = (px * px + py * py).sqrt();
+                        if dist > radius || dist < inner {
+                            continue;
+                        }
+                        let angle = px.atan2(-py).rem_euclid(2.0 * PI);
+                        if let Some(i) = self.slice_at(angle, total) {
+                            bits |= bit;
+                            counts[i] += 1;
+                        }
+                    }
+                }
+                if bits == 0 {
+                    continue;
+                }
+                let dominant = counts
+                    .iter()
+                    .enumerate()
+                    .max_by_key(|(_, c)| **c)
+                    .map(|(i, _)| i)
+                    .unwrap_or_default();
+                let symbol = if unicode == UnicodeLevel::Full {
+                    char::from_u32(0x2800 + bits)
+                } else {
+                    (bits.count_ones() >= 4).then_some(fill)
+                };
+                if let Some(symbol) = symbol {
+                    buf.get_mut(area.x + col, area.y + row)
+                        .set_char(symbol)
+                        .set_fg(self.slices[dominant].color);
+                }
+            }
+        }
+    }
+}
+
+impl Widget for PieChart {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let total = self.total();
+        if area.is_empty() || total <= 0.0 {
+            return;
+        }
+
+        let legend = self.legend_lines(total);
+        let legend_width = legend.iter().map(|l| l.width()).max().unwrap_or(0) as u16 + 1;
+        let (chart_rect, legend_rect) = if area.width >= legend_width + LEGEND_MIN_CHART_WIDTH {
+            let layout = Layout::horizontal([
+                Constraint::Min(LEGEND_MIN_CHART_WIDTH),
+                Constraint::Length(legend_width),
+            ])
+            .split(area);
+            (layout[0], Some(layout[1]))
+        } else {
+            (area, None)
+        };
+
+        self.render_chart(chart_rect, buf, total);
+        if let Some(r) = legend_rect {
+            let offset = r.height.saturating_sub(legend.len() as u16) / 2;
+            let r = Rect::new(r.x + 1, r.y + offset, r.width - 1, r.height - offset);
+            Paragraph::new(legend).render(r, buf);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_clipped_to_buffer() {
+        let chart = PieChart::new(vec![
+            PieSlice::new("a".to_string(), 1.0, None, 0),
+            PieSlice::new("b".to_string(), 2.0, None, 1),
+        ]);
+        let blank = Buffer::filled(
+            Rect::new(0, 0, 20, 5),
+            buffer::Cell::default().set_symbol("x"),
+        );
+        let mut buf = blank.clone();
+        chart.clone().render(Rect::new(10, 2, 30, 10), &mut buf);
+        let clip = Rect::new(10, 2, 10, 3);
+        for (i, cell) in buf.content.iter().enumerate() {
+            let (x, y) = buf.pos_of(i);
+            if !clip.intersects(Rect::new(x, y, 1, 1)) {
+                assert_eq!(cell, blank.get(x, y), "drawn outside at ({x}, {y})");
+            }
+        }
+        assert_ne!(buf, blank);
+
+        // -- an area past the buffer draws nothing
+        let mut buf = blank.clone();
+        chart.render(Rect::new(30, 10, 10, 5), &mut buf);
+        assert_eq!(buf, blank);
+    }
+}
@@ -0,0 +1,125 @@
+use ratatui::{
+    prelude::*,
+    symbols::Marker,
+    widgets::{Axis, Chart, Dataset, GraphType},
+};
+
+use crate::{
+    capabilities::{capabilities, UnicodeLevel},
+    locale::locale,
+};
+
+/// Points from CSV, one `y` column (x is the row number) or `x,y` columns.
+/// Lines which do not parse, like header, are skipped.
+pub fn parse_points(csv: &str) -> Vec<(f64, f64)> {
+    let mut points = vec![];
+    for line in csv.lines() {
+        let values: Option<Vec<f64>> = line
+            .split(',')
+            .map(|v| v.trim().parse::<f64>().ok())
+            .collect();
+        match values.as_deref() {
+            Some([y]) => points.push((points.len() as f64, *y)),
+            Some([x, y, ..]) => points.push((*x, *y)),
+            _ => {}
+        }
+    }
+    points
+}
+
+fn bounds(values: impl Iterator<Item = f64>) -> [f64; 2] {
+    let (min, max) = values.fold((f64::INFINITY, f64::NEG_INFINITY), |(min, max), v| {
+        (min.min(v), max.max(v))
+    });
+    if min > max {
+        [0.0, 1.0]
+    } else if min == max {
+        [min - 1.0, max + 1.0]
+    } else {
+        [min, max]
+    }
+}
+
+fn label(value: f64) -> Span<'static> {
+    let decimals = if value.fract() == 0.0 { 0 } else { 2 };
+    let text = match locale() {
+        Some(locale) => locale.number(value, decimals),
+        None => format!("{:.*}", decimals, value),
+    };
+    Span::styled(text, Style::default().fg(Color::Rgb(100, 100, 100)))
+}
+
+/// High resolution scatter or line plot drawn with braille dots.
+pub struct PlotWidget {
+    points: Vec<(f64, f64)>,
+    color: Color,
+    line: bool,
+}
+
+impl PlotWidget {
+    pub fn new(points: Vec<(f64, f64)>, color: Color) -> Self {
+        Self {
+            points,
+            color,
+            line: false,
+        }
+    }
+
+    pub fn line(mut self, line: bool) -> Self {
+        self.line = line;
+        self
+    }
+}
+
+impl Widget for PlotWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let x_bounds = bounds(self.points.iter().map(|p| p.0));
+        let y_bounds = bounds(self.points.iter().map(|p| p.1));
+        let marker = if capabilities().unicode == UnicodeLevel::Full {
+            Marker::Braille
+        } else {
+            Marker::Block
+        };
+        let graph_type = if self.line {
+            GraphType::Line
+        } else {
+            GraphType::Scatter
+        };
+        let dataset = Dataset::default()
+            .marker(marker)
+            .graph_type(graph_type)
+            .style(Style::default().fg(self.color))
+            .data(&self.points);
+        let axis_style = Style::default().fg(Color::Rgb(100, 100, 100));
+        Chart::new(vec![dataset])
+            .x_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds(x_bounds)
+                    .labels(vec![label(x_bounds[0]), label(x_bounds[1])]),
+            )
+            .y_axis(
+                Axis::default()
+                    .style(axis_style)
+                    .bounds(y_bounds)
+                    .labels(vec![label(y_bounds[0]), label(y_bounds[1])]),
+            )
+            .render(area, buf);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_parse_points() {
+        assert_eq!(parse_points("y\n1\n2.5\n"), vec![(0.0, 1.0), (1.0, 2.5)]);
+        assert_eq!(
+            parse_points("x, y\n0.5, 1\nbad\n2, -3, 7\n"),
+            vec![(0.5, 1.0), (2.0, -3.0)]
+        );
+    }
+}
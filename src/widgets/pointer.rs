@@ -0,0 +1,53 @@
+use ratatui::prelude::*;
+
+/// Laser pointer dot drawn over the slide at `position`, the cell keeps its background.
+pub struct Pointer {
+    position: (u16, u16),
+}
+
+impl Pointer {
+    pub fn new(position: (u16, u16)) -> Self {
+        Self { position }
+    }
+}
+
+/// Position moved by `dx` columns and `dy` rows, kept inside `area`.
+pub fn moved(position: (u16, u16), dx: i16, dy: i16, area: Rect) -> (u16, u16) {
+    let clamp = |value: u16, delta: i16, start: u16, end: u16| {
+        value
+            .saturating_add_signed(delta)
+            .clamp(start, end.saturating_sub(1).max(start))
+    };
+    (
+        clamp(position.0, dx, area.left(), area.right()),
+        clamp(position.1, dy, area.top(), area.bottom()),
+    )
+}
+
+impl Widget for Pointer {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let (x, y) = self.position;
+        if !area.contains(layout::Position::new(x, y)) {
+            return;
+        }
+        let cell = buf.get_mut(x, y);
+        cell.set_symbol("●");
+        cell.modifier.insert(Modifier::BOLD);
+        cell.set_fg(Color::LightRed);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_moved() {
+        let area = Rect::new(10, 5, 20, 10);
+        assert_eq!(moved((12, 7), 2, -1, area), (14, 6));
+        assert_eq!(moved((12, 7), -5, -5, area), (10, 5));
+        assert_eq!(moved((28, 13), 4, 4, area), (29, 14));
+    }
+}
@@ -0,0 +1,82 @@
+use ratatui::prelude::*;
+
+/// Part of the deck shown, current slide included. `budgets` are the slides of the
+/// flow with their `duration_secs` when weighted, slides without one count as the
+/// average of those with one.
+pub fn deck_progress(budgets: &[Option<u64>], shown: usize, weighted: bool) -> f64 {
+    if budgets.is_empty() {
+        return 0.0;
+    }
+    let known: Vec<u64> = budgets.iter().flatten().copied().collect();
+    if !weighted || known.is_empty() {
+        return shown.min(budgets.len()) as f64 / budgets.len() as f64;
+    }
+    let average = known.iter().sum::<u64>() as f64 / known.len() as f64;
+    let weight = |budget: &Option<u64>| budget.map_or(average, |b| b as f64);
+    let total: f64 = budgets.iter().map(weight).sum();
+    if total == 0.0 {
+        return 0.0;
+    }
+    budgets.iter().take(shown).map(weight).sum::<f64>() / total
+}
+
+/// Bottom border of the slide box drawn heavier up to `ratio` of its width, titles on
+/// the border are kept.
+pub struct ProgressBar<'a> {
+    ratio: f64,
+    /// Glyph of the border, only cells showing it are drawn over.
+    border: &'a str,
+}
+
+impl<'a> ProgressBar<'a> {
+    pub fn new(ratio: f64, border: &'a str) -> Self {
+        Self { ratio, border }
+    }
+}
+
+impl Widget for ProgressBar<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if area.height == 0 {
+            return;
+        }
+        let y = area.bottom() - 1;
+        let filled = (area.width as f64 * self.ratio.clamp(0.0, 1.0)).round() as u16;
+        for x in area.left()..area.left() + filled {
+            let cell = buf.get_mut(x, y);
+            if cell.symbol() == self.border {
+                cell.set_symbol("━").set_fg(Color::Green);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_deck_progress() {
+        assert_eq!(deck_progress(&[None; 4], 1, false), 0.25);
+        assert_eq!(deck_progress(&[None; 4], 4, true), 1.0);
+        assert_eq!(deck_progress(&[], 0, false), 0.0);
+        let budgets = [Some(60), Some(180), None, Some(120)];
+        assert_eq!(deck_progress(&budgets, 1, true), 60.0 / 480.0);
+        assert_eq!(deck_progress(&budgets, 3, true), 360.0 / 480.0);
+        assert_eq!(deck_progress(&budgets, 1, false), 0.25);
+
+        let mut buf = Buffer::with_lines(vec!["╰──|1/4|──╯"]);
+        ProgressBar::new(0.5, "─").render(buf.area, &mut buf);
+        assert_eq!(buf.get(1, 0).symbol(), "━");
+        assert_eq!(buf.get(3, 0).symbol(), "|");
+        assert_eq!(buf.get(0, 0).symbol(), "╰");
+        assert_eq!(buf.get(8, 0).symbol(), "─");
+
+        // -- slide box running past the terminal edge
+        let mut buf = Buffer::with_lines(vec!["╰────"]);
+        ProgressBar::new(1.0, "─").render(Rect::new(0, 0, 10, 3), &mut buf);
+        assert_eq!(buf.get(4, 0).symbol(), "━");
+    }
+}
@@ -0,0 +1,72 @@
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+};
+
+pub enum PromptEvent {
+    Submit(String),
+    Cancel,
+    Changed,
+}
+
+/// One-line text input rendered as a small bordered box.
+#[derive(Debug, Clone, Default)]
+pub struct InputPrompt {
+    title: String,
+    value: String,
+}
+
+impl InputPrompt {
+    pub fn new(title: &str) -> Self {
+        Self {
+            title: title.to_string(),
+            value: String::new(),
+        }
+    }
+
+    /// Start with `value` typed in.
+    pub fn with_value(mut self, value: &str) -> Self {
+        self.value = value.to_string();
+        self
+    }
+
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    pub fn handle_key(&mut self, key: KeyEvent) -> PromptEvent {
+        match key.code {
+            KeyCode::Enter => PromptEvent::Submit(std::mem::take(&mut self.value)),
+            KeyCode::Esc => PromptEvent::Cancel,
+            KeyCode::Char('c') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                PromptEvent::Cancel
+            }
+            KeyCode::Backspace => {
+                self.value.pop();
+                PromptEvent::Changed
+            }
+            KeyCode::Char(c) => {
+                self.value.push(c);
+                PromptEvent::Changed
+            }
+            _ => PromptEvent::Changed,
+        }
+    }
+}
+
+impl Widget for &InputPrompt {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Yellow))
+            .title(format!(" {} ", self.title));
+        let line = Line::from(vec![
+            Span::raw(self.value.clone()),
+            Span::styled(" ", Style::default().bg(Color::White)),
+        ]);
+        Paragraph::new(line).block(block).render(area, buf);
+    }
+}
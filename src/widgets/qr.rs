@@ -0,0 +1,128 @@
+use qrcode::{Color as QrColor, QrCode};
+use ratatui::{prelude::*, widgets::Paragraph};
+
+use crate::capabilities::{capabilities, UnicodeLevel};
+
+const QUIET_ZONE: usize = 1;
+
+/// QR code drawn with half-block characters, two modules per terminal cell.
+#[derive(Debug, Clone, Default)]
+pub struct QrWidget {
+    width: usize,
+    modules: Vec<bool>,
+    error: Option<String>,
+}
+
+impl QrWidget {
+    pub fn new(data: &str) -> Self {
+        match QrCode::new(data.as_bytes()) {
+            Ok(code) => Self {
+                width: code.width(),
+                modules: code
+                    .to_colors()
+                    .into_iter()
+                    .map(|c| c == QrColor::Dark)
+                    .collect(),
+                error: None,
+            },
+            Err(e) => Self {
+                error: Some(format!("QR code error: {}", e)),
+                ..Default::default()
+            },
+        }
+    }
+
+    /// Dark module at position including the quiet zone around the code.
+    fn is_dark(&self, x: usize, y: usize) -> bool {
+        let size = self.width + QUIET_ZONE * 2;
+        if x < QUIET_ZONE || y < QUIET_ZONE || x >= size - QUIET_ZONE || y >= size - QUIET_ZONE {
+            return false;
+        }
+        self.modules[(y - QUIET_ZONE) * self.width + (x - QUIET_ZONE)]
+    }
+}
+
+impl Widget for QrWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        if let Some(e) = &self.error {
+            Paragraph::new(e.clone()).red().render(area, buf);
+            return;
+        }
+
+        let size = self.width + QUIET_ZONE * 2;
+        // -- without block characters every module is two colored spaces
+        let half_blocks = capabilities().unicode >= UnicodeLevel::Basic;
+        let (cols, rows) = if half_blocks {
+            (size as u16, size.div_ceil(2) as u16)
+        } else {
+            (size as u16 * 2, size as u16)
+        };
+        if cols > area.width || rows > area.height {
+            Paragraph::new(format!("QR code needs {}x{} cells", cols, rows))
+                .red()
+                .render(area, buf);
+            return;
+        }
+
+        let x_offset = area.x + (area.width - cols) / 2;
+        let y_offset = area.y + (area.height - rows) / 2;
+        let color = |dark: bool| if dark { Color::Black } else { Color::White };
+        if !half_blocks {
+            for row in 0..size {
+                for col in 0..size {
+                    for dx in 0..2 {
+                        buf.get_mut(x_offset + col as u16 * 2 + dx, y_offset + row as u16)
+                            .set_char(' ')
+                            .set_bg(color(self.is_dark(col, row)));
+                    }
+                }
+            }
+            return;
+        }
+        for row in 0..rows as usize {
+            for col in 0..size {
+                let top = self.is_dark(col, row * 2);
+                let bottom = row * 2 + 1 < size && self.is_dark(col, row * 2 + 1);
+                let bottom_color = if row * 2 + 1 < size {
+                    color(bottom)
+                } else {
+                    Color::Reset
+                };
+                buf.get_mut(x_offset + col as u16, y_offset + row as u16)
+                    .set_char('▀')
+                    .set_fg(color(top))
+                    .set_bg(bottom_color);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_render_clipped_to_buffer() {
+        let qr = QrWidget::new("https://example.com");
+        let area = Rect::new(0, 0, 20, 5);
+        let untouched = Buffer::filled(area, buffer::Cell::default().set_symbol("x"));
+        let mut buf = untouched.clone();
+        qr.clone().render(Rect::new(10, 2, 60, 40), &mut buf);
+        assert_eq!(buf.get(10, 2).symbol(), "Q");
+        let outside = buf
+            .content
+            .iter()
+            .zip(&untouched.content)
+            .enumerate()
+            .filter(|(_, (drawn, before))| drawn != before)
+            .map(|(i, _)| buf.pos_of(i))
+            .find(|&(x, y)| x < 10 || y < 2);
+        assert_eq!(outside, None);
+
+        // -- and nothing for an area wholly outside it
+        let mut buf = untouched.clone();
+        qr.render(Rect::new(25, 0, 10, 5), &mut buf);
+        assert_eq!(buf, untouched);
+    }
+}
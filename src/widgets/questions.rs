@@ -0,0 +1,50 @@
+use ratatui::{
+    prelude::*,
+    widgets::{List, ListItem, ListState},
+};
+
+use crate::{
+    capabilities::{capabilities, UnicodeLevel},
+    notes::Note,
+};
+
+/// Q&A queue listing captured notes, answered ones are crossed out.
+pub struct QuestionList<'a> {
+    notes: &'a [Note],
+    selected: usize,
+}
+
+impl<'a> QuestionList<'a> {
+    pub fn new(notes: &'a [Note], selected: usize) -> Self {
+        Self { notes, selected }
+    }
+}
+
+impl Widget for QuestionList<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if self.notes.is_empty() {
+            Line::from("No questions yet".dark_gray()).render(area, buf);
+            return;
+        }
+
+        let caps = capabilities();
+        let checked = caps.glyph(UnicodeLevel::Full, "☑", "[x]");
+        let unchecked = caps.glyph(UnicodeLevel::Full, "☐", "[ ]");
+        let items: Vec<ListItem> = self
+            .notes
+            .iter()
+            .map(|n| {
+                if n.answered {
+                    ListItem::new(format!("{} {}", checked, n.text))
+                        .style(Style::default().dark_gray().crossed_out())
+                } else {
+                    ListItem::new(format!("{} {}", unchecked, n.text))
+                        .style(Style::default().white())
+                }
+            })
+            .collect();
+        let list = List::new(items).highlight_style(Style::default().yellow().bold());
+        let mut state = ListState::default().with_selected(Some(self.selected));
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
@@ -0,0 +1,54 @@
+use ratatui::prelude::*;
+
+use crate::capabilities::{capabilities, UnicodeLevel};
+
+/// Horizontal or vertical line through the middle of its area.
+pub struct RuleWidget {
+    symbol: Option<String>,
+    color: Color,
+    vertical: bool,
+}
+
+impl RuleWidget {
+    pub fn new(symbol: Option<String>, color: Color) -> Self {
+        Self {
+            symbol: symbol.filter(|s| !s.is_empty()),
+            color,
+            vertical: false,
+        }
+    }
+
+    pub fn vertical(mut self, vertical: bool) -> Self {
+        self.vertical = vertical;
+        self
+    }
+}
+
+impl Widget for RuleWidget {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        if area.is_empty() {
+            return;
+        }
+        let caps = capabilities();
+        let symbol = match (&self.symbol, self.vertical) {
+            (Some(symbol), _) => symbol.as_str(),
+            (None, false) => caps.glyph(UnicodeLevel::Basic, "─", "-"),
+            (None, true) => caps.glyph(UnicodeLevel::Basic, "│", "|"),
+        };
+        let style = Style::default().fg(self.color);
+        if self.vertical {
+            let x = area.x + area.width / 2;
+            for y in area.top()..area.bottom() {
+                buf.set_stringn(x, y, symbol, 1, style);
+            }
+        } else {
+            let y = area.y + area.height / 2;
+            let width = symbol.chars().count().max(1) as u16;
+            let mut x = area.x;
+            while x + width <= area.right() {
+                buf.set_string(x, y, symbol, style);
+                x += width;
+            }
+        }
+    }
+}
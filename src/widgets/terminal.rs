@@ -0,0 +1,134 @@
+use ratatui::{prelude::*, widgets::*};
+
+use crate::capabilities::capabilities;
+
+/// Screen of embedded terminal inside a block titled with the command.
+pub struct TerminalWidget<'a> {
+    screen: &'a vt100::Screen,
+    command: &'a str,
+    focused: bool,
+}
+
+fn color(color: vt100::Color) -> Option<Color> {
+    match color {
+        vt100::Color::Default => None,
+        vt100::Color::Idx(i) => Some(Color::Indexed(i)),
+        vt100::Color::Rgb(r, g, b) => Some(capabilities().color(Color::Rgb(r, g, b))),
+    }
+}
+
+impl<'a> TerminalWidget<'a> {
+    pub fn new(screen: &'a vt100::Screen, command: &'a str) -> Self {
+        Self {
+            screen,
+            command,
+            focused: false,
+        }
+    }
+
+    /// Focused terminal gets highlighted border and shows its cursor.
+    pub fn focused(mut self, focused: bool) -> Self {
+        self.focused = focused;
+        self
+    }
+}
+
+impl Widget for TerminalWidget<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        let area = area.intersection(buf.area);
+        let border = if self.focused {
+            Color::Yellow
+        } else {
+            Color::Rgb(100, 100, 100)
+        };
+        let command = if self.command.trim().is_empty() {
+            "shell"
+        } else {
+            self.command
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(border))
+            .title(format!(" $ {} ", command));
+        let inner = block.inner(area);
+        block.render(area, buf);
+
+        let (rows, cols) = self.screen.size();
+        for row in 0..rows.min(inner.height) {
+            for col in 0..cols.min(inner.width) {
+                let Some(cell) = self.screen.cell(row, col) else {
+                    continue;
+                };
+                let mut style = Style::default();
+                if let Some(fg) = color(cell.fgcolor()) {
+                    style = style.fg(fg);
+                }
+                if let Some(bg) = color(cell.bgcolor()) {
+                    style = style.bg(bg);
+                }
+                if cell.bold() {
+                    style = style.add_modifier(Modifier::BOLD);
+                }
+                if cell.italic() {
+                    style = style.add_modifier(Modifier::ITALIC);
+                }
+                if cell.underline() {
+                    style = style.add_modifier(Modifier::UNDERLINED);
+                }
+                if cell.inverse() {
+                    style = style.add_modifier(Modifier::REVERSED);
+                }
+                let target = buf.get_mut(inner.x + col, inner.y + row);
+                if cell.is_wide_continuation() {
+                    target.set_skip(true);
+                    continue;
+                }
+                match cell.contents().as_str() {
+                    "" => target.set_symbol(" "),
+                    s => target.set_symbol(s),
+                };
+                target.set_style(style);
+            }
+        }
+
+        let (row, col) = self.screen.cursor_position();
+        if self.focused && !self.screen.hide_cursor() && row < inner.height && col < inner.width {
+            buf.get_mut(inner.x + col, inner.y + row)
+                .set_style(Style::default().add_modifier(Modifier::REVERSED));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Buffer of `x` so cells left alone can be told from those drawn.
+    fn marked() -> Buffer {
+        Buffer::filled(
+            Rect::new(0, 0, 20, 5),
+            buffer::Cell::default().set_symbol("x"),
+        )
+    }
+
+    #[test]
+    fn test_render_clipped_to_buffer() {
+        let mut parser = vt100::Parser::new(10, 40, 0);
+        parser.process(b"hello from the shell");
+        let mut buf = marked();
+        TerminalWidget::new(parser.screen(), "sh").render(Rect::new(10, 2, 30, 10), &mut buf);
+        assert_eq!(buf.get(11, 3).symbol(), "h");
+        for y in 0..5 {
+            for x in 0..20 {
+                if x < 10 || y < 2 {
+                    assert_eq!(buf.get(x, y).symbol(), "x", "drawn outside at ({x}, {y})");
+                }
+            }
+        }
+
+        // -- nothing at all is drawn for an area past the buffer
+        let mut buf = marked();
+        TerminalWidget::new(parser.screen(), "sh").render(Rect::new(30, 10, 10, 5), &mut buf);
+        assert_eq!(buf, marked());
+    }
+}
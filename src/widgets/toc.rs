@@ -0,0 +1,46 @@
+use ratatui::{
+    prelude::*,
+    widgets::{Block, BorderType, Borders, Clear, List, ListItem, ListState},
+};
+
+/// Outline of the deck, slide numbers and titles with the current slide highlighted.
+pub struct Toc<'a> {
+    titles: &'a [Option<String>],
+    current: usize,
+}
+
+impl<'a> Toc<'a> {
+    pub fn new(titles: &'a [Option<String>], current: usize) -> Self {
+        Self { titles, current }
+    }
+}
+
+impl Widget for Toc<'_> {
+    fn render(self, area: Rect, buf: &mut Buffer) {
+        Clear.render(area, buf);
+        let width = self.titles.len().to_string().len();
+        let items: Vec<ListItem> = self
+            .titles
+            .iter()
+            .enumerate()
+            .map(|(i, title)| {
+                let number = format!("{:>width$} ", i + 1).dark_gray();
+                let title = match title {
+                    Some(title) => title.clone().into(),
+                    None => "untitled".dark_gray().italic(),
+                };
+                ListItem::new(Line::from(vec![number, title]))
+            })
+            .collect();
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(Style::default().fg(Color::Rgb(100, 100, 100)))
+            .title(" Outline ");
+        let list = List::new(items)
+            .block(block)
+            .highlight_style(Style::default().fg(Color::Black).bg(Color::Green));
+        let mut state = ListState::default().with_selected(Some(self.current));
+        StatefulWidget::render(list, area, buf, &mut state);
+    }
+}
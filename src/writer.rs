@@ -0,0 +1,223 @@
+use std::path::{Path, PathBuf};
+
+use crate::{
+    enums::{SlideJson, SlidesJson},
+    error::{io, Error, Result},
+};
+
+/// Change of edit mode to slide of the deck as written in the file.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlideEdit {
+    MoveLeft,
+    MoveRight,
+    Duplicate,
+    Delete,
+}
+
+/// Apply `edit` to slide at `origin` (see `SlideJson::origin`) of `slides` as written
+/// in the file, sub-slides move within their section and a section moves with its
+/// sub-slides. Origin of the slide to show afterwards, `None` when nothing changed.
+pub fn edit_slide(
+    slides: &mut Vec<SlideJson>,
+    origin: (usize, usize),
+    edit: SlideEdit,
+) -> Option<(usize, usize)> {
+    let (index, sub) = origin;
+    if sub == 0 {
+        return edit_list(slides, index, edit, 1).map(|index| (index, 0));
+    }
+    let section = slides.get_mut(index)?;
+    let children = section.children.as_mut()?;
+    let child = edit_list(children, sub - 1, edit, 0)?;
+    if children.is_empty() {
+        section.children = None;
+        return Some((index, 0));
+    }
+    Some((index, child + 1))
+}
+
+/// Edit of list of slides keeping at least `min_len` of them.
+fn edit_list(
+    list: &mut Vec<SlideJson>,
+    index: usize,
+    edit: SlideEdit,
+    min_len: usize,
+) -> Option<usize> {
+    if index >= list.len() {
+        return None;
+    }
+    match edit {
+        SlideEdit::MoveLeft if index > 0 => {
+            list.swap(index, index - 1);
+            Some(index - 1)
+        }
+        SlideEdit::MoveRight if index + 1 < list.len() => {
+            list.swap(index, index + 1);
+            Some(index + 1)
+        }
+        SlideEdit::Duplicate => {
+            list.insert(index + 1, list[index].clone());
+            Some(index + 1)
+        }
+        SlideEdit::Delete if list.len() > min_len => {
+            list.remove(index);
+            Some(index.min(list.len().saturating_sub(1)))
+        }
+        _ => None,
+    }
+}
+
+/// Serialize slides to pretty JSON. Fields are written in schema order and unknown
+/// fields are kept in their order, so writing a loaded deck back produces minimal diff.
+pub fn slides_to_string(slides: &SlidesJson) -> Result<String> {
+    let mut out = serde_json::to_string_pretty(slides).map_err(|e| Error::Parse(e.to_string()))?;
+    out.push('\n');
+    Ok(out)
+}
+
+/// File edited deck is saved to, JSON and JSON5 decks are written back in place and
+/// text formats next to the source as JSON.
+pub fn save_path(json_slides: &str) -> PathBuf {
+    let path = Path::new(json_slides);
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_lowercase());
+    match extension.as_deref() {
+        Some("json" | "json5") => path.to_path_buf(),
+        _ => path.with_extension("json"),
+    }
+}
+
+/// Write slides to JSON file, content is written to temporary file first and then
+/// renamed so the deck is never left half written.
+pub fn write_slides(path: &Path, slides: &SlidesJson) -> Result<()> {
+    let out = slides_to_string(slides)?;
+    let mut tmp_path = path.as_os_str().to_owned();
+    tmp_path.push(".tmp");
+    let context = format!("failed to write slides file '{}'", path.display());
+    std::fs::write(&tmp_path, out).map_err(io(&context))?;
+    std::fs::rename(&tmp_path, path).map_err(io(context))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use pretty_assertions::assert_eq;
+
+    use super::*;
+
+    #[test]
+    fn test_round_trip_keeps_unknown_fields() {
+        let json = r##"{
+  "box_size": {
+    "width": 90,
+    "height": 35
+  },
+  "slides": [
+    {
+      "title": "RATATUI",
+      "content": [
+        {
+          "type": "Line",
+          "content": "hello",
+          "color": "#FFFF00",
+          "my_tool_id": 7
+        }
+      ],
+      "author_comment": "keep me"
+    }
+  ],
+  "version": 2
+}
+"##;
+        let slides: SlidesJson = serde_json::from_str(json).unwrap();
+        assert_eq!(slides.slides[0].content[0].extra["my_tool_id"], 7);
+        assert_eq!(slides_to_string(&slides).unwrap(), json);
+    }
+
+    #[test]
+    fn test_round_trip_keeps_order_of_unknown_fields() {
+        let json = r##"{
+  "box_size": {
+    "width": 90,
+    "height": 35
+  },
+  "slides": [
+    {
+      "title": "ORDER",
+      "content": [],
+      "zeta": 1,
+      "alpha": 2,
+      "mid": 3
+    }
+  ]
+}
+"##;
+        let slides: SlidesJson = serde_json::from_str(json).unwrap();
+        assert_eq!(slides_to_string(&slides).unwrap(), json);
+    }
+
+    #[test]
+    fn test_save_path() {
+        assert_eq!(
+            save_path("talk/slides.json"),
+            PathBuf::from("talk/slides.json")
+        );
+        assert_eq!(
+            save_path(".data/slides.json5"),
+            PathBuf::from(".data/slides.json5")
+        );
+        assert_eq!(save_path("talk.org"), PathBuf::from("talk.json"));
+    }
+
+    #[test]
+    fn test_edit_slide() {
+        let slide = |title: &str| SlideJson::new(Some(title.to_string()), vec![]);
+        let titles = |slides: &[SlideJson]| -> Vec<String> {
+            slides.iter().map(|s| s.title.clone().unwrap()).collect()
+        };
+        let mut slides = vec![slide("A"), slide("B"), slide("C")];
+        assert_eq!(
+            edit_slide(&mut slides, (1, 0), SlideEdit::MoveLeft),
+            Some((0, 0))
+        );
+        assert_eq!(titles(&slides), ["B", "A", "C"]);
+        assert_eq!(edit_slide(&mut slides, (2, 0), SlideEdit::MoveRight), None);
+        assert_eq!(
+            edit_slide(&mut slides, (2, 0), SlideEdit::Duplicate),
+            Some((3, 0))
+        );
+        assert_eq!(
+            edit_slide(&mut slides, (0, 0), SlideEdit::Delete),
+            Some((0, 0))
+        );
+        assert_eq!(titles(&slides), ["A", "C", "C"]);
+
+        let mut last = vec![slide("A")];
+        assert_eq!(edit_slide(&mut last, (0, 0), SlideEdit::Delete), None);
+
+        let mut section = slide("S");
+        section.children = Some(vec![slide("S1"), slide("S2")]);
+        let mut slides = vec![slide("A"), section];
+        assert_eq!(
+            edit_slide(&mut slides, (1, 1), SlideEdit::MoveRight),
+            Some((1, 2))
+        );
+        assert_eq!(titles(slides[1].children.as_ref().unwrap()), ["S2", "S1"]);
+        assert_eq!(
+            edit_slide(&mut slides, (1, 0), SlideEdit::MoveLeft),
+            Some((0, 0))
+        );
+        assert_eq!(slides[0].children.as_ref().unwrap().len(), 2);
+        assert_eq!(
+            edit_slide(&mut slides, (0, 2), SlideEdit::Delete),
+            Some((0, 1))
+        );
+        assert_eq!(
+            edit_slide(&mut slides, (0, 1), SlideEdit::Delete),
+            Some((0, 0))
+        );
+        assert_eq!(slides[0].children, None);
+    }
+}